@@ -25,7 +25,15 @@ pub fn flatten_jsarray(input: js_sys::Array, shape: &mut Vec<usize>) -> js_sys::
 pub enum JsType {
     Number(f64),
     Array(js_sys::Array),
+    Int8Array(js_sys::Int8Array),
+    Uint8Array(js_sys::Uint8Array),
+    Int16Array(js_sys::Int16Array),
+    Uint16Array(js_sys::Uint16Array),
     Int32Array(js_sys::Int32Array),
+    Uint32Array(js_sys::Uint32Array),
+    BigInt64Array(js_sys::BigInt64Array),
+    BigUint64Array(js_sys::BigUint64Array),
+    Float32Array(js_sys::Float32Array),
     Float64Array(js_sys::Float64Array),
 }
 
@@ -34,13 +42,46 @@ pub enum JsType {
 /// Can be unwraped into:
 /// - a number
 /// - an Array
-/// - a Int32Array
-/// - a Float64Array
+/// - a typed array of one of the types of JsType. A Uint8ClampedArray is unwrapped as Uint8Array.
 pub fn unwrap_js_value(input: JsValue) -> Result<JsType, JsValue> {
     if let Some(number) = input.as_f64() {
         Ok(JsType::Number(number))
     } else if input.is_instance_of::<js_sys::Array>() {
         Ok(JsType::Array(input.unchecked_into::<js_sys::Array>()))
+    } else if input.is_instance_of::<js_sys::Int8Array>() {
+        Ok(JsType::Int8Array(
+            input.unchecked_into::<js_sys::Int8Array>(),
+        ))
+    } else if input.is_instance_of::<js_sys::Uint8Array>() {
+        Ok(JsType::Uint8Array(
+            input.unchecked_into::<js_sys::Uint8Array>(),
+        ))
+    } else if input.is_instance_of::<js_sys::Uint8ClampedArray>() {
+        Ok(JsType::Uint8Array(js_sys::Uint8Array::new(&input)))
+    } else if input.is_instance_of::<js_sys::Int16Array>() {
+        Ok(JsType::Int16Array(
+            input.unchecked_into::<js_sys::Int16Array>(),
+        ))
+    } else if input.is_instance_of::<js_sys::Uint16Array>() {
+        Ok(JsType::Uint16Array(
+            input.unchecked_into::<js_sys::Uint16Array>(),
+        ))
+    } else if input.is_instance_of::<js_sys::Uint32Array>() {
+        Ok(JsType::Uint32Array(
+            input.unchecked_into::<js_sys::Uint32Array>(),
+        ))
+    } else if input.is_instance_of::<js_sys::BigInt64Array>() {
+        Ok(JsType::BigInt64Array(
+            input.unchecked_into::<js_sys::BigInt64Array>(),
+        ))
+    } else if input.is_instance_of::<js_sys::BigUint64Array>() {
+        Ok(JsType::BigUint64Array(
+            input.unchecked_into::<js_sys::BigUint64Array>(),
+        ))
+    } else if input.is_instance_of::<js_sys::Float32Array>() {
+        Ok(JsType::Float32Array(
+            input.unchecked_into::<js_sys::Float32Array>(),
+        ))
     } else if input.is_instance_of::<js_sys::Int32Array>() {
         Ok(JsType::Int32Array(
             input.unchecked_into::<js_sys::Int32Array>(),
@@ -58,7 +99,6 @@ pub fn unwrap_js_value(input: JsValue) -> Result<JsType, JsValue> {
 pub fn into_vec_usize(input: &js_sys::Array) -> Result<Vec<usize>, JsValue> {
     input
         .iter()
-        .into_iter()
         .map(|x: JsValue| match x.as_f64() {
             Some(n) => Ok(n as usize),
             None => Err(JsValue::from_str("Indices must be only numbers")),
@@ -66,11 +106,40 @@ pub fn into_vec_usize(input: &js_sys::Array) -> Result<Vec<usize>, JsValue> {
         .collect()
 }
 
+/// Turns a Javascript number into the length of an axis, which must be a non-negative integer.
+pub fn into_dim(input: f64) -> Result<usize, JsValue> {
+    if input >= 0.0 && input.fract() == 0.0 && input < usize::MAX as f64 {
+        Ok(input as usize)
+    } else {
+        Err(JsValue::from_str(&format!(
+            "Dimension {} is not a non-negative integer.",
+            input
+        )))
+    }
+}
+
+/// Turns a Javascript Array into a shape. The dimensions must be non-negative integers and their product, the number of elements, must fit into a `usize`.
+pub fn into_shape(input: &js_sys::Array) -> Result<Vec<usize>, JsValue> {
+    let shape = input
+        .iter()
+        .map(|x: JsValue| match x.as_f64() {
+            Some(n) => into_dim(n),
+            None => Err(JsValue::from_str("Dimensions must be only numbers")),
+        })
+        .collect::<Result<Vec<usize>, JsValue>>()?;
+    match shape
+        .iter()
+        .try_fold(1usize, |size, &dim| size.checked_mul(dim))
+    {
+        Some(_) => Ok(shape),
+        None => Err(JsValue::from_str("Shape has too many elements.")),
+    }
+}
+
 /// Turns a Javascript Array into a `Vec<isize>`
 pub fn into_vec_isize(input: &js_sys::Array) -> Result<Vec<isize>, JsValue> {
     input
         .iter()
-        .into_iter()
         .map(|x: JsValue| match x.as_f64() {
             Some(n) => Ok(n as isize),
             None => Err(JsValue::from_str("Indices must be only numbers")),
@@ -78,25 +147,29 @@ pub fn into_vec_isize(input: &js_sys::Array) -> Result<Vec<isize>, JsValue> {
         .collect()
 }
 
-pub fn vec_f64_into_float64array(input: Vec<f64>) -> js_sys::Float64Array {
-    js_sys::Float64Array::from(input.as_slice())
-}
-
-pub fn vec_isize_into_array(input: Vec<isize>) -> js_sys::Array {
+/// Turns a Javascript Array of numbers, `null` and `undefined` into a `Vec<Option<isize>>`, where `null` and `undefined` become `None`.
+pub fn into_vec_option_isize(input: &js_sys::Array) -> Result<Vec<Option<isize>>, JsValue> {
     input
-        .into_iter()
-        .map(|x| JsValue::from_f64(x as f64))
+        .iter()
+        .map(|x: JsValue| {
+            if x.is_null() || x.is_undefined() {
+                Ok(None)
+            } else {
+                match x.as_f64() {
+                    Some(n) => Ok(Some(n as isize)),
+                    None => Err(JsValue::from_str(
+                        "Indices must be only numbers, null or undefined",
+                    )),
+                }
+            }
+        })
         .collect()
 }
 
-pub fn vecvec_isize_into_arrayarray(input: Vec<Vec<isize>>) -> js_sys::Array {
+pub fn vec_isize_into_array(input: Vec<isize>) -> js_sys::Array {
     input
         .into_iter()
-        .map(|x| {
-            x.into_iter()
-                .map(|y| JsValue::from_f64(y as f64))
-                .collect::<js_sys::Array>()
-        })
+        .map(|x| JsValue::from_f64(x as f64))
         .collect()
 }
 
@@ -6,6 +6,22 @@ use wasm_bindgen_test::*;
 use crate::js_interop::*;
 
 wasm_bindgen_test_configure!(run_in_browser);
+
+pub fn vec_f64_into_float64array(input: Vec<f64>) -> js_sys::Float64Array {
+    js_sys::Float64Array::from(input.as_slice())
+}
+
+pub fn vecvec_isize_into_arrayarray(input: Vec<Vec<isize>>) -> js_sys::Array {
+    input
+        .into_iter()
+        .map(|x| {
+            x.into_iter()
+                .map(|y| JsValue::from_f64(y as f64))
+                .collect::<js_sys::Array>()
+        })
+        .collect()
+}
+
 #[wasm_bindgen_test]
 fn test_vec_isize_into_array() {
     let vec: Vec<isize> = (1..17).collect();
@@ -45,3 +61,13 @@ fn test_into_vec_usize() {
 
     assert_eq!(jsarray.get(6).as_f64().unwrap() as usize, vec[6]);
 }
+
+#[wasm_bindgen_test]
+fn test_into_shape() {
+    let jsarray = vec_isize_into_array(vec![4, 0, 3]);
+    assert_eq!(into_shape(&jsarray).unwrap(), vec![4, 0, 3]);
+    assert!(into_shape(&vec_isize_into_array(vec![4, -1])).is_err());
+    assert!(into_dim(2.5).is_err());
+    assert!(into_dim(f64::INFINITY).is_err());
+    assert_eq!(into_dim(7.0).unwrap(), 7);
+}
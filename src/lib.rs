@@ -14,14 +14,9 @@
 
 mod iter;
 mod js_interop;
+mod linalg;
 pub mod ndarray;
+mod random;
 mod utils;
 
-use wasm_bindgen::prelude::*;
-
 pub use ndarray::*;
-
-// #[wasm_bindgen(start)]
-// pub fn main() {
-//     utils::set_panic_hook();
-// }
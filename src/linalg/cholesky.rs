@@ -0,0 +1,96 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Computes the lower triangular factor `L` of the Cholesky decomposition `a = L L^T` of a n x n matrix stored in row-major order.
+///
+/// Only the lower triangle of `a` is read, so the matrix is assumed to be symmetric. Fails if the matrix is not positive definite.
+pub fn cholesky_base(a: &[f64], n: usize) -> Result<Vec<f64>, String> {
+    let mut l = vec![0.0; n * n];
+    for j in 0..n {
+        let diagonal = a[j * n + j] - (0..j).map(|p| l[j * n + p] * l[j * n + p]).sum::<f64>();
+        if diagonal <= 0.0 || diagonal.is_nan() {
+            return Err(format!(
+                "Matrix is not positive definite: the leading minor of order {} is not positive.",
+                j + 1
+            ));
+        }
+        let diagonal = diagonal.sqrt();
+        l[j * n + j] = diagonal;
+        for i in j + 1..n {
+            let sum = (0..j).map(|p| l[i * n + p] * l[j * n + p]).sum::<f64>();
+            l[i * n + j] = (a[i * n + j] - sum) / diagonal;
+        }
+    }
+    Ok(l)
+}
+
+/// Solves `L L^T x = b` for a lower triangular n x n matrix `L` and a n x k right-hand side `b` by forward and backward substitution.
+pub fn cholesky_solve(l: &[f64], b: &[f64], n: usize, k: usize) -> Vec<f64> {
+    let mut x = b.to_vec();
+    for i in 0..n {
+        for p in 0..i {
+            for j in 0..k {
+                x[i * k + j] -= l[i * n + p] * x[p * k + j];
+            }
+        }
+        for j in 0..k {
+            x[i * k + j] /= l[i * n + i];
+        }
+    }
+    for i in (0..n).rev() {
+        for p in i + 1..n {
+            for j in 0..k {
+                x[i * k + j] -= l[p * n + i] * x[p * k + j];
+            }
+        }
+        for j in 0..k {
+            x[i * k + j] /= l[i * n + i];
+        }
+    }
+    x
+}
+
+/// Returns the transpose of a n x n matrix stored in row-major order.
+fn transpose(a: &[f64], n: usize) -> Vec<f64> {
+    (0..n * n).map(|x| a[(x % n) * n + x / n]).collect()
+}
+
+/// Cholesky decomposition of a (stack of) symmetric positive-definite matrices.
+///
+/// Returns the lower triangular matrix `L` with `A = L L^T`, or the upper triangular matrix `U = L^T` with `A = U^T U` if `upper` is set. Only the lower triangle of `A` is read. Throws an error if a matrix is not positive definite.
+#[wasm_bindgen]
+pub fn cholesky(a: &Ndarray, upper: Option<bool>) -> Result<Ndarray, JsValue> {
+    let a = to_f64(a);
+    let n = square_shape(&a.shape)
+        .map_err(|err| JsValue::from_str(&err))?
+        .1;
+    into_ndarray(map_matrices(&a, &[&[n, n]], |matrix, _, _| {
+        let l = cholesky_base(matrix, n)?;
+        Ok(vec![if upper.unwrap_or(false) {
+            transpose(&l, n)
+        } else {
+            l
+        }])
+    }))
+}
+
+/// Solves the linear system `A x = b` for a (stack of) symmetric positive-definite matrices `A`, given their Cholesky factors computed by `cholesky`. `b` is a vector or a (stack of) matrices.
+///
+/// `upper` has to match the argument the factors were computed with. Reusing the factors makes solving for multiple right-hand sides much cheaper than `solve`.
+#[wasm_bindgen(js_name = choSolve)]
+pub fn cho_solve(factor: &Ndarray, b: &Ndarray, upper: Option<bool>) -> Result<Ndarray, JsValue> {
+    let upper = upper.unwrap_or(false);
+    map_systems(&to_f64(factor), &to_f64(b), |factor, b, n, k| {
+        if (0..n).any(|i| factor[i * n + i] == 0.0) {
+            return Err(String::from("Cholesky factor is singular."));
+        }
+        if upper {
+            Ok(cholesky_solve(&transpose(factor, n), b, n, k))
+        } else {
+            Ok(cholesky_solve(factor, b, n, k))
+        }
+    })
+    .map(|x| Ndarray(NdarrayUnion::F64(x)))
+    .map_err(|err| JsValue::from_str(&err))
+}
@@ -0,0 +1,527 @@
+use wasm_bindgen::prelude::*;
+
+use std::ops::Range;
+
+use super::*;
+
+/// Maximum number of Jacobi sweeps before `symmetric_eigen` gives up.
+const MAX_SWEEPS: usize = 100;
+
+/// Computes the eigenvalues and eigenvectors of a symmetric n x n matrix stored in row-major order with the cyclic Jacobi method.
+///
+/// Only the lower triangle of `a` is read. Returns the eigenvalues in ascending order and a matrix in row-major order whose columns are the corresponding orthonormal eigenvectors.
+pub fn symmetric_eigen(a: &[f64], n: usize) -> Result<(Vec<f64>, Vec<f64>), String> {
+    let mut a = (0..n * n)
+        .map(|x| {
+            let (i, j) = (x / n, x % n);
+            a[i.max(j) * n + i.min(j)]
+        })
+        .collect::<Vec<f64>>();
+    let mut v = identity(n);
+    let norm = a.iter().map(|x| x * x).sum::<f64>();
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let off = (0..n)
+            .flat_map(|i| (0..i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i * n + j] * a[i * n + j])
+            .sum::<f64>();
+        if off <= f64::EPSILON * f64::EPSILON * norm {
+            converged = true;
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let g = 100.0 * a[p * n + q].abs();
+                // elements that are negligible compared to both diagonal elements are dropped
+                if a[p * n + p].abs() + g == a[p * n + p].abs()
+                    && a[q * n + q].abs() + g == a[q * n + q].abs()
+                {
+                    a[p * n + q] = 0.0;
+                    a[q * n + p] = 0.0;
+                    continue;
+                }
+                // rotation in the (p, q) plane that zeroes a[p][q]
+                let theta = (a[q * n + q] - a[p * n + p]) / (2.0 * a[p * n + q]);
+                let t = if theta.is_infinite() {
+                    0.5 / theta
+                } else {
+                    theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt())
+                };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (x, y) = (a[k * n + p], a[k * n + q]);
+                    a[k * n + p] = c * x - s * y;
+                    a[k * n + q] = s * x + c * y;
+                }
+                for k in 0..n {
+                    let (x, y) = (a[p * n + k], a[q * n + k]);
+                    a[p * n + k] = c * x - s * y;
+                    a[q * n + k] = s * x + c * y;
+                }
+                a[p * n + q] = 0.0;
+                a[q * n + p] = 0.0;
+                for k in 0..n {
+                    let (x, y) = (v[k * n + p], v[k * n + q]);
+                    v[k * n + p] = c * x - s * y;
+                    v[k * n + q] = s * x + c * y;
+                }
+            }
+        }
+    }
+    if !converged {
+        return Err(String::from("Eigenvalues did not converge."));
+    }
+    let mut order = (0..n).collect::<Vec<usize>>();
+    order.sort_by(|&i, &j| a[i * n + i].partial_cmp(&a[j * n + j]).unwrap());
+    let values = order.iter().map(|&i| a[i * n + i]).collect();
+    let vectors = (0..n * n).map(|x| v[(x / n) * n + order[x % n]]).collect();
+    Ok((values, vectors))
+}
+
+/// Reduces a n x n matrix stored in row-major order to upper Hessenberg form with Householder reflections. The eigenvalues are preserved.
+fn hessenberg(a: &mut [f64], n: usize) {
+    for k in 0..n.saturating_sub(2) {
+        let norm = (k + 1..n)
+            .map(|i| a[i * n + k] * a[i * n + k])
+            .sum::<f64>()
+            .sqrt();
+        if norm == 0.0 {
+            continue;
+        }
+        let x0 = a[(k + 1) * n + k];
+        let alpha = if x0 > 0.0 { -norm } else { norm };
+        // Householder vector v with H = I - 2 v v^T / (v^T v)
+        let mut v = vec![0.0; n];
+        v[k + 1] = x0 - alpha;
+        for i in k + 2..n {
+            v[i] = a[i * n + k];
+        }
+        let vv = v.iter().map(|x| x * x).sum::<f64>();
+        for j in 0..n {
+            let w = 2.0 * (k + 1..n).map(|i| v[i] * a[i * n + j]).sum::<f64>() / vv;
+            for i in k + 1..n {
+                a[i * n + j] -= w * v[i];
+            }
+        }
+        for i in 0..n {
+            let w = 2.0 * (k + 1..n).map(|j| a[i * n + j] * v[j]).sum::<f64>() / vv;
+            for j in k + 1..n {
+                a[i * n + j] -= w * v[j];
+            }
+        }
+    }
+}
+
+/// Maximum number of QR steps per eigenvalue before `hessenberg_eigenvalues` gives up.
+const MAX_QR_STEPS: usize = 40;
+
+/// Computes the Householder reflection `I - beta v v^T` that maps `x` to a multiple of the first unit vector. Returns `v` and `beta`, which is zero if `x` is zero.
+fn householder(x: &[f64]) -> (Vec<f64>, f64) {
+    let norm = x.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let mut v = x.to_vec();
+    if norm == 0.0 {
+        return (v, 0.0);
+    }
+    // subtracting the multiple with the opposite sign avoids cancellation
+    v[0] += norm.copysign(x[0]);
+    let vv = v.iter().map(|x| x * x).sum::<f64>();
+    (v, 2.0 / vv)
+}
+
+/// Applies the reflection `I - beta v v^T` to rows `k..k + v.len()` of the columns `columns` of a n x n matrix in row-major order from the left.
+fn reflect_rows(h: &mut [f64], n: usize, v: &[f64], beta: f64, k: usize, columns: Range<usize>) {
+    for j in columns {
+        let d = beta
+            * v.iter()
+                .enumerate()
+                .map(|(i, v)| v * h[(k + i) * n + j])
+                .sum::<f64>();
+        for (i, v) in v.iter().enumerate() {
+            h[(k + i) * n + j] -= d * v;
+        }
+    }
+}
+
+/// Applies the reflection `I - beta v v^T` to columns `k..k + v.len()` of the rows `rows` of a n x n matrix in row-major order from the right.
+fn reflect_columns(h: &mut [f64], n: usize, v: &[f64], beta: f64, k: usize, rows: Range<usize>) {
+    for i in rows {
+        let d = beta
+            * v.iter()
+                .enumerate()
+                .map(|(j, v)| h[i * n + k + j] * v)
+                .sum::<f64>();
+        for (j, v) in v.iter().enumerate() {
+            h[i * n + k + j] -= d * v;
+        }
+    }
+}
+
+/// Returns the eigenvalues of the 2 x 2 matrix `[[a, b], [c, d]]` as pairs of real and imaginary part. A complex pair has the positive imaginary part first.
+fn eigenvalues_2x2(a: f64, b: f64, c: f64, d: f64) -> [(f64, f64); 2] {
+    let p = 0.5 * (a - d);
+    let discriminant = p * p + b * c;
+    if discriminant >= 0.0 {
+        // the root with the larger magnitude first, the second one from the product of both
+        let z = p + discriminant.sqrt().copysign(p);
+        if z == 0.0 {
+            [(d, 0.0), (d, 0.0)]
+        } else {
+            [(d + z, 0.0), (d - b * c / z, 0.0)]
+        }
+    } else {
+        let im = (-discriminant).sqrt();
+        [(d + p, im), (d + p, -im)]
+    }
+}
+
+/// Computes the eigenvalues of an upper Hessenberg n x n matrix in row-major order with the Francis double-shift QR algorithm, following Golub and Van Loan, Matrix Computations, algorithms 7.5.1 and 7.5.2.
+///
+/// The active window `lo..hi` shrinks whenever a subdiagonal element becomes negligible, which splits off eigenvalues of 1 x 1 and 2 x 2 blocks at its end. Returns the real and imaginary parts of the eigenvalues. Complex conjugate pairs are adjacent with the positive imaginary part first.
+fn hessenberg_eigenvalues(h: &mut [f64], n: usize) -> Result<(Vec<f64>, Vec<f64>), String> {
+    let mut wr = vec![0.0; n];
+    let mut wi = vec![0.0; n];
+    let norm = h.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let mut hi = n;
+    let mut steps = 0;
+    while hi > 0 {
+        // the last negligible subdiagonal element of the window splits it
+        let lo = (1..hi)
+            .rev()
+            .find(|&l| {
+                let mut scale = h[(l - 1) * n + l - 1].abs() + h[l * n + l].abs();
+                if scale == 0.0 {
+                    scale = norm;
+                }
+                h[l * n + l - 1].abs() <= f64::EPSILON * scale
+            })
+            .unwrap_or(0);
+        if lo > 0 {
+            h[lo * n + lo - 1] = 0.0;
+        }
+        if lo + 2 >= hi {
+            if lo + 1 == hi {
+                wr[lo] = h[lo * n + lo];
+            } else {
+                let values = eigenvalues_2x2(
+                    h[lo * n + lo],
+                    h[lo * n + lo + 1],
+                    h[(lo + 1) * n + lo],
+                    h[(lo + 1) * n + lo + 1],
+                );
+                for (i, (re, im)) in values.iter().enumerate() {
+                    wr[lo + i] = *re;
+                    wi[lo + i] = *im;
+                }
+            }
+            hi = lo;
+            steps = 0;
+            continue;
+        }
+        if steps == MAX_QR_STEPS {
+            return Err(String::from("Eigenvalues did not converge."));
+        }
+        steps += 1;
+        // the shifts are the eigenvalues of the trailing 2 x 2 block, given by their sum and product
+        let m = hi - 1;
+        let (mut sum, mut product) = (
+            h[(m - 1) * n + m - 1] + h[m * n + m],
+            h[(m - 1) * n + m - 1] * h[m * n + m] - h[(m - 1) * n + m] * h[m * n + m - 1],
+        );
+        if steps % 10 == 0 {
+            // shifts next to the trailing diagonal element break cycles that the standard shifts can fall into
+            let w = h[m * n + m - 1].abs() + h[(m - 1) * n + m - 2].abs();
+            let center = h[m * n + m] + w;
+            sum = 2.0 * center;
+            product = center * center + w * w;
+        }
+        // first column of (H - s1 I)(H - s2 I), which only has three nonzero elements
+        let (h00, h01, h10, h11, h21) = (
+            h[lo * n + lo],
+            h[lo * n + lo + 1],
+            h[(lo + 1) * n + lo],
+            h[(lo + 1) * n + lo + 1],
+            h[(lo + 2) * n + lo + 1],
+        );
+        let mut x = vec![
+            h00 * h00 + h01 * h10 - sum * h00 + product,
+            h10 * (h00 + h11 - sum),
+            h10 * h21,
+        ];
+        // chase the bulge that the first reflection creates down the subdiagonal
+        for k in lo..hi - 2 {
+            let (v, beta) = householder(&x);
+            let first = if k > lo { k - 1 } else { lo };
+            reflect_rows(h, n, &v, beta, k, first..hi);
+            reflect_columns(h, n, &v, beta, k, lo..(k + 4).min(hi));
+            if k > lo {
+                h[(k + 1) * n + k - 1] = 0.0;
+                h[(k + 2) * n + k - 1] = 0.0;
+            }
+            x = (k + 1..(k + 4).min(hi)).map(|i| h[i * n + k]).collect();
+        }
+        let (v, beta) = householder(&x);
+        reflect_rows(h, n, &v, beta, hi - 2, hi - 3..hi);
+        reflect_columns(h, n, &v, beta, hi - 2, lo..hi);
+        h[(hi - 1) * n + hi - 3] = 0.0;
+    }
+    Ok((wr, wi))
+}
+
+/// Returns the largest absolute value of the entries of a matrix.
+fn max_abs(a: &[f64]) -> f64 {
+    a.iter().fold(0.0f64, |max, x| max.max(x.abs()))
+}
+
+/// Computes the eigenvalues of a general n x n matrix stored in row-major order. Returns their real and imaginary parts.
+///
+/// The iteration works on the matrix divided by its largest entry, so that very small or large matrices don't underflow or overflow.
+pub fn eigenvalues(a: &[f64], n: usize) -> Result<(Vec<f64>, Vec<f64>), String> {
+    let scale = max_abs(a);
+    if scale == 0.0 {
+        return Ok((vec![0.0; n], vec![0.0; n]));
+    }
+    let mut a = a.iter().map(|x| x / scale).collect::<Vec<f64>>();
+    hessenberg(&mut a, n);
+    let (wr, wi) = hessenberg_eigenvalues(&mut a, n)?;
+    Ok((
+        wr.into_iter().map(|x| x * scale).collect(),
+        wi.into_iter().map(|x| x * scale).collect(),
+    ))
+}
+
+/// Solves `(a - lambda I) x = b` for a real n x n matrix `a` and a complex `lambda` and `b` with Gaussian elimination and partial pivoting.
+///
+/// Complex numbers are passed as pairs of real and imaginary part. Pivots that vanish are replaced by `epsilon`, so the nearly singular systems of inverse iteration can be solved.
+fn shifted_solve(
+    a: &[f64],
+    n: usize,
+    lambda: (f64, f64),
+    b: &[(f64, f64)],
+    epsilon: f64,
+) -> Vec<(f64, f64)> {
+    let mul = |x: (f64, f64), y: (f64, f64)| (x.0 * y.0 - x.1 * y.1, x.0 * y.1 + x.1 * y.0);
+    let div = |x: (f64, f64), y: (f64, f64)| {
+        let d = y.0 * y.0 + y.1 * y.1;
+        ((x.0 * y.0 + x.1 * y.1) / d, (x.1 * y.0 - x.0 * y.1) / d)
+    };
+    let abs = |x: (f64, f64)| x.0.hypot(x.1);
+    let mut m = (0..n * n)
+        .map(|x| {
+            let (i, j) = (x / n, x % n);
+            if i == j {
+                (a[x] - lambda.0, -lambda.1)
+            } else {
+                (a[x], 0.0)
+            }
+        })
+        .collect::<Vec<(f64, f64)>>();
+    let mut x = b.to_vec();
+    for k in 0..n {
+        let p = (k..n).fold(k, |p, i| {
+            if abs(m[i * n + k]) > abs(m[p * n + k]) {
+                i
+            } else {
+                p
+            }
+        });
+        if p != k {
+            for j in 0..n {
+                m.swap(k * n + j, p * n + j);
+            }
+            x.swap(k, p);
+        }
+        if abs(m[k * n + k]) == 0.0 {
+            m[k * n + k] = (epsilon, 0.0);
+        }
+        for i in k + 1..n {
+            let factor = div(m[i * n + k], m[k * n + k]);
+            for j in k..n {
+                let y = mul(factor, m[k * n + j]);
+                m[i * n + j] = (m[i * n + j].0 - y.0, m[i * n + j].1 - y.1);
+            }
+            let y = mul(factor, x[k]);
+            x[i] = (x[i].0 - y.0, x[i].1 - y.1);
+        }
+    }
+    for i in (0..n).rev() {
+        let mut sum = x[i];
+        for j in i + 1..n {
+            let y = mul(m[i * n + j], x[j]);
+            sum = (sum.0 - y.0, sum.1 - y.1);
+        }
+        x[i] = div(sum, m[i * n + i]);
+    }
+    x
+}
+
+/// Approximates the eigenvector of the eigenvalue `lambda` of a n x n matrix with three steps of inverse iteration.
+///
+/// The start vector depends on `seed`, so that different eigenvectors are found for repeated eigenvalues. Components along the unit vectors in `others` are removed in every step.
+fn inverse_iteration(
+    a: &[f64],
+    n: usize,
+    lambda: (f64, f64),
+    seed: usize,
+    others: &[&[(f64, f64)]],
+    epsilon: f64,
+) -> Result<Vec<(f64, f64)>, String> {
+    let mut x = (0..n)
+        .map(|i| (((i * 7 + seed * 13) % 11 + 1) as f64, 0.0))
+        .collect::<Vec<(f64, f64)>>();
+    for _ in 0..3 {
+        x = shifted_solve(a, n, lambda, &x, epsilon);
+        for other in others {
+            let (dot_re, dot_im) = other
+                .iter()
+                .zip(x.iter())
+                .fold((0.0, 0.0), |(re, im), (v, y)| {
+                    (re + v.0 * y.0 + v.1 * y.1, im + v.0 * y.1 - v.1 * y.0)
+                });
+            for (y, v) in x.iter_mut().zip(other.iter()) {
+                y.0 -= dot_re * v.0 - dot_im * v.1;
+                y.1 -= dot_re * v.1 + dot_im * v.0;
+            }
+        }
+        let norm = x.iter().map(|y| y.0 * y.0 + y.1 * y.1).sum::<f64>().sqrt();
+        if norm == 0.0 || !norm.is_finite() {
+            return Err(String::from("Eigenvectors did not converge."));
+        }
+        for y in x.iter_mut() {
+            *y = (y.0 / norm, y.1 / norm);
+        }
+    }
+    Ok(x)
+}
+
+/// Returns the norm of `a x - lambda x` for a real n x n matrix `a` and a complex vector `x`.
+fn residual(a: &[f64], n: usize, lambda: (f64, f64), x: &[(f64, f64)]) -> f64 {
+    (0..n)
+        .map(|i| {
+            let (re, im) = (0..n).fold((0.0, 0.0), |(re, im), j| {
+                (re + a[i * n + j] * x[j].0, im + a[i * n + j] * x[j].1)
+            });
+            let re = re - (lambda.0 * x[i].0 - lambda.1 * x[i].1);
+            let im = im - (lambda.0 * x[i].1 + lambda.1 * x[i].0);
+            re * re + im * im
+        })
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Computes the eigenvalues and right eigenvectors of a general n x n matrix stored in row-major order.
+///
+/// Returns the real and imaginary parts of the eigenvalues and of a matrix in row-major order whose columns are the eigenvectors. The eigenvectors are computed by inverse iteration, normalized to unit length and rotated so that their largest component is real. Eigenvectors of (nearly) repeated eigenvalues are orthogonalized against each other unless the matrix is defective.
+pub fn eigen(a: &[f64], n: usize) -> Result<EigenResult, String> {
+    let scale = max_abs(a);
+    if scale == 0.0 {
+        // every vector is an eigenvector of the zero matrix
+        return Ok(EigenResult {
+            wr: vec![0.0; n],
+            wi: vec![0.0; n],
+            vr: identity(n),
+            vi: vec![0.0; n * n],
+        });
+    }
+    // the eigenvectors don't depend on the scale of the matrix, so they are computed for the matrix divided by its largest entry, which keeps `epsilon` away from underflow
+    let a = &a.iter().map(|x| x / scale).collect::<Vec<f64>>()[..];
+    let (wr, wi) = eigenvalues(a, n)?;
+    let epsilon = f64::EPSILON * a.iter().map(|x| x.abs()).sum::<f64>();
+    let mut vectors: Vec<Vec<(f64, f64)>> = Vec::with_capacity(n);
+    for e in 0..n {
+        if wi[e] < 0.0 && e > 0 && wi[e - 1] == -wi[e] && wr[e - 1] == wr[e] {
+            // complex conjugate of the previous eigenvector
+            let conjugate = vectors[e - 1].iter().map(|&(re, im)| (re, -im)).collect();
+            vectors.push(conjugate);
+            continue;
+        }
+        let similar = (0..e)
+            .filter(|&f| (wr[f] - wr[e]).hypot(wi[f] - wi[e]) <= 1e3 * epsilon)
+            .collect::<Vec<usize>>();
+        let others = similar.iter().map(|&f| &vectors[f][..]).collect::<Vec<_>>();
+        let lambda = (wr[e], wi[e]);
+        let mut x = inverse_iteration(a, n, lambda, e, &others, epsilon)?;
+        // defective matrices don't have independent eigenvectors for repeated eigenvalues
+        if !others.is_empty() && residual(a, n, lambda, &x) > 1e3 * epsilon {
+            x = inverse_iteration(a, n, lambda, e, &[], epsilon)?;
+        }
+        // rotate the phase so that the largest component is real and positive
+        let largest = x.iter().fold((0.0f64, 0.0f64), |largest, &y| {
+            if y.0.hypot(y.1) > largest.0.hypot(largest.1) {
+                y
+            } else {
+                largest
+            }
+        });
+        let length = largest.0.hypot(largest.1);
+        let phase = (largest.0 / length, -largest.1 / length);
+        for y in x.iter_mut() {
+            *y = (y.0 * phase.0 - y.1 * phase.1, y.0 * phase.1 + y.1 * phase.0);
+        }
+        vectors.push(x);
+    }
+    let vr = (0..n * n).map(|x| vectors[x % n][x / n].0).collect();
+    let vi = (0..n * n).map(|x| vectors[x % n][x / n].1).collect();
+    Ok(EigenResult {
+        wr: wr.into_iter().map(|x| x * scale).collect(),
+        wi: wi.into_iter().map(|x| x * scale).collect(),
+        vr,
+        vi,
+    })
+}
+
+/// Eigenvalues and eigenvectors of a general matrix, split into real and imaginary parts.
+pub struct EigenResult {
+    pub wr: Vec<f64>,
+    pub wi: Vec<f64>,
+    pub vr: Vec<f64>,
+    pub vi: Vec<f64>,
+}
+
+/// Eigenvalues and eigenvectors of a (stack of) real symmetric matrices. Only the lower triangle is read.
+///
+/// Returns an Array `[w, v]` with the eigenvalues `w` in ascending order and a matrix `v` whose columns are the corresponding orthonormal eigenvectors, so that `A v = v diag(w)`.
+#[wasm_bindgen]
+pub fn eigh(a: &Ndarray) -> Result<js_sys::Array, JsValue> {
+    let a = to_f64(a);
+    let n = square_shape(&a.shape)
+        .map_err(|err| JsValue::from_str(&err))?
+        .1;
+    let parts = map_matrices(&a, &[&[n], &[n, n]], |matrix, _, _| {
+        let (values, vectors) = symmetric_eigen(matrix, n)?;
+        Ok(vec![values, vectors])
+    });
+    Ok(into_js_values(parts)?.into_iter().collect())
+}
+
+/// Eigenvalues and right eigenvectors of a (stack of) real square matrices.
+///
+/// Returns an Array `[wr, wi, vr, vi]` with the real and imaginary parts of the eigenvalues and of a matrix whose columns are the eigenvectors, which are normalized to unit length. Complex eigenvalues come in conjugate pairs with the positive imaginary part first. The eigenvalues are not ordered.
+#[wasm_bindgen]
+pub fn eig(a: &Ndarray) -> Result<js_sys::Array, JsValue> {
+    let a = to_f64(a);
+    let n = square_shape(&a.shape)
+        .map_err(|err| JsValue::from_str(&err))?
+        .1;
+    let parts = map_matrices(&a, &[&[n], &[n], &[n, n], &[n, n]], |matrix, _, _| {
+        let result = eigen(matrix, n)?;
+        Ok(vec![result.wr, result.wi, result.vr, result.vi])
+    });
+    Ok(into_js_values(parts)?.into_iter().collect())
+}
+
+/// Eigenvalues of a (stack of) real square matrices.
+///
+/// Returns an Array `[wr, wi]` with the real and imaginary parts of the eigenvalues. Complex eigenvalues come in conjugate pairs with the positive imaginary part first.
+#[wasm_bindgen]
+pub fn eigvals(a: &Ndarray) -> Result<js_sys::Array, JsValue> {
+    let a = to_f64(a);
+    let n = square_shape(&a.shape)
+        .map_err(|err| JsValue::from_str(&err))?
+        .1;
+    let parts = map_matrices(&a, &[&[n], &[n]], |matrix, _, _| {
+        let (wr, wi) = eigenvalues(matrix, n)?;
+        Ok(vec![wr, wi])
+    });
+    Ok(into_js_values(parts)?.into_iter().collect())
+}
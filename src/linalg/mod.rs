@@ -0,0 +1,335 @@
+use wasm_bindgen::prelude::*;
+
+use crate::ndarray::*;
+
+pub mod cholesky;
+pub mod eig;
+pub mod qr;
+pub mod svd;
+pub mod test;
+
+/// Converts the elements of a Ndarray to floating point numbers. A Ndarray that already has type `float64` shares its data.
+pub fn to_f64(ndarray: &Ndarray) -> NdarrayBase<f64> {
+    ndarray.0.convert::<f64>()
+}
+
+/// Splits the shape of a stack of matrices into the shape of the stack and the number of rows and columns of the matrices.
+pub fn matrix_shape(shape: &[usize]) -> Result<(&[usize], usize, usize), String> {
+    if shape.len() < 2 {
+        Err(format!(
+            "Array of dimension {} is not a (stack of) matrices. At least two dimensions are required.",
+            shape.len()
+        ))
+    } else {
+        let ndim = shape.len();
+        Ok((&shape[..ndim - 2], shape[ndim - 2], shape[ndim - 1]))
+    }
+}
+
+/// Like `matrix_shape`, but requires the matrices to be square and returns their size.
+pub fn square_shape(shape: &[usize]) -> Result<(&[usize], usize), String> {
+    let (batch, rows, cols) = matrix_shape(shape)?;
+    if rows == cols {
+        Ok((batch, rows))
+    } else {
+        Err(format!(
+            "Last two dimensions of the array must be square, but the matrices have shape {}x{}.",
+            rows, cols
+        ))
+    }
+}
+
+/// Applies `f` to every matrix in the last two axes of `ndarray`.
+///
+/// `f` receives a matrix stored contigiously in row-major order together with its number of rows and columns, and returns one or more parts. Part `i` of all matrices is stacked into an array with the leading axes of `ndarray` followed by `shapes[i]`.
+pub fn map_matrices(
+    ndarray: &NdarrayBase<f64>,
+    shapes: &[&[usize]],
+    f: impl Fn(&[f64], usize, usize) -> Result<Vec<Vec<f64>>, String>,
+) -> Result<Vec<NdarrayBase<f64>>, String> {
+    let (batch, rows, cols) = matrix_shape(&ndarray.shape)?;
+    let data = ndarray.to_vec();
+    let count = batch.iter().product::<usize>();
+    let mut parts = shapes
+        .iter()
+        .map(|shape| Vec::with_capacity(count * shape.iter().product::<usize>()))
+        .collect::<Vec<Vec<f64>>>();
+    for i in 0..count {
+        let matrix = &data[i * rows * cols..(i + 1) * rows * cols];
+        for (part, result) in parts.iter_mut().zip(f(matrix, rows, cols)?) {
+            part.extend(result);
+        }
+    }
+    Ok(parts
+        .into_iter()
+        .zip(shapes.iter())
+        .map(|(part, shape)| {
+            NdarrayBase::from_vec(part, batch.iter().chain(shape.iter()).cloned().collect())
+        })
+        .collect())
+}
+
+/// Returns the identity matrix of size `n` in row-major order.
+pub fn identity(n: usize) -> Vec<f64> {
+    let mut data = vec![0.0; n * n];
+    for i in 0..n {
+        data[i * n + i] = 1.0;
+    }
+    data
+}
+
+/// LU decomposition with partial pivoting of a square matrix.
+pub struct Lu {
+    /// Factors L (below the diagonal, with an implicit unit diagonal) and U (on and above the diagonal) in row-major order.
+    pub lu: Vec<f64>,
+    /// Row `i` of `L U` is row `perm[i]` of the decomposed matrix.
+    pub perm: Vec<usize>,
+    /// Parity of the row permutation, either `1.0` or `-1.0`.
+    pub sign: f64,
+    pub n: usize,
+}
+
+impl Lu {
+    /// Decomposes a square matrix stored in row-major order. Singular matrices are decomposed as well, the zero pivots remain on the diagonal of U.
+    pub fn new(a: &[f64], n: usize) -> Lu {
+        let mut lu = a.to_vec();
+        let mut perm = (0..n).collect::<Vec<usize>>();
+        let mut sign = 1.0;
+        for k in 0..n {
+            // the row with the largest absolute value in column k becomes the pivot row
+            let p = (k..n).fold(k, |p, i| {
+                if lu[i * n + k].abs() > lu[p * n + k].abs() {
+                    i
+                } else {
+                    p
+                }
+            });
+            if p != k {
+                for j in 0..n {
+                    lu.swap(k * n + j, p * n + j);
+                }
+                perm.swap(k, p);
+                sign = -sign;
+            }
+            let pivot = lu[k * n + k];
+            if pivot != 0.0 {
+                for i in k + 1..n {
+                    let factor = lu[i * n + k] / pivot;
+                    lu[i * n + k] = factor;
+                    for j in k + 1..n {
+                        lu[i * n + j] -= factor * lu[k * n + j];
+                    }
+                }
+            }
+        }
+        Lu { lu, perm, sign, n }
+    }
+
+    pub fn is_singular(&self) -> bool {
+        (0..self.n).any(|i| self.lu[i * self.n + i] == 0.0)
+    }
+
+    pub fn det(&self) -> f64 {
+        (0..self.n).fold(self.sign, |det, i| det * self.lu[i * self.n + i])
+    }
+
+    /// Returns the sign and the natural logarithm of the absolute value of the determinant.
+    pub fn slogdet(&self) -> (f64, f64) {
+        if self.is_singular() {
+            return (0.0, f64::NEG_INFINITY);
+        }
+        (0..self.n).fold((self.sign, 0.0), |(sign, logdet), i| {
+            let x = self.lu[i * self.n + i];
+            (sign * x.signum(), logdet + x.abs().ln())
+        })
+    }
+
+    /// Solves `A x = b` for a right-hand side `b` with `k` columns stored in row-major order. The matrix must not be singular.
+    pub fn solve(&self, b: &[f64], k: usize) -> Vec<f64> {
+        let n = self.n;
+        let mut x = self
+            .perm
+            .iter()
+            .flat_map(|&i| b[i * k..(i + 1) * k].iter().cloned())
+            .collect::<Vec<f64>>();
+        for i in 0..n {
+            for p in 0..i {
+                let factor = self.lu[i * n + p];
+                for j in 0..k {
+                    x[i * k + j] -= factor * x[p * k + j];
+                }
+            }
+        }
+        for i in (0..n).rev() {
+            for p in i + 1..n {
+                let factor = self.lu[i * n + p];
+                for j in 0..k {
+                    x[i * k + j] -= factor * x[p * k + j];
+                }
+            }
+            let pivot = self.lu[i * n + i];
+            for j in 0..k {
+                x[i * k + j] /= pivot;
+            }
+        }
+        x
+    }
+}
+
+/// Solves the systems of linear equations with the stack of square matrices `a` and the right-hand sides `b` with `f`.
+///
+/// `b` is either a vector or a stack of matrices whose leading axes are broadcast against the leading axes of `a`. `f` receives a n x n matrix and a n x k right-hand side stored contigiously in row-major order and returns the n x k solution.
+pub fn map_systems(
+    a: &NdarrayBase<f64>,
+    b: &NdarrayBase<f64>,
+    f: impl Fn(&[f64], &[f64], usize, usize) -> Result<Vec<f64>, String>,
+) -> Result<NdarrayBase<f64>, String> {
+    let (a_batch, n) = square_shape(&a.shape)?;
+    let vector = b.shape.len() == 1;
+    let b_matrix;
+    let b = if vector {
+        b_matrix = NdarrayBase::from_vec(b.to_vec(), vec![b.shape[0], 1]);
+        &b_matrix
+    } else {
+        b
+    };
+    let (b_batch, rows, k) = matrix_shape(&b.shape)?;
+    if rows != n {
+        return Err(format!(
+            "Right-hand side with {} rows doesn't fit matrices of size {}x{}.",
+            rows, n, n
+        ));
+    }
+    let batch = broadcast_shapes(&[a_batch, b_batch])?;
+    let a_data = a.broadcast_to(&[&batch[..], &[n, n]].concat())?.to_vec();
+    let b_data = b.broadcast_to(&[&batch[..], &[n, k]].concat())?.to_vec();
+    let mut data = Vec::with_capacity(b_data.len());
+    for i in 0..batch.iter().product::<usize>() {
+        data.extend(f(
+            &a_data[i * n * n..(i + 1) * n * n],
+            &b_data[i * n * k..(i + 1) * n * k],
+            n,
+            k,
+        )?);
+    }
+    let mut shape = batch;
+    shape.push(n);
+    if !vector {
+        shape.push(k);
+    }
+    Ok(NdarrayBase::from_vec(data, shape))
+}
+
+/// Solves the systems of linear equations `a x = b` for a stack of square matrices `a` with LU decompositions.
+pub fn solve_base(a: &NdarrayBase<f64>, b: &NdarrayBase<f64>) -> Result<NdarrayBase<f64>, String> {
+    map_systems(a, b, |a, b, n, k| {
+        let lu = Lu::new(a, n);
+        if lu.is_singular() {
+            Err(String::from("Matrix is singular."))
+        } else {
+            Ok(lu.solve(b, k))
+        }
+    })
+}
+
+/// Converts the parts computed by a linear algebra routine into javascript values. Parts without dimensions are returned as numbers.
+fn into_js_values(result: Result<Vec<NdarrayBase<f64>>, String>) -> Result<Vec<JsValue>, JsValue> {
+    result
+        .map(|parts| {
+            parts
+                .into_iter()
+                .map(|part| Ndarray(NdarrayUnion::F64(part)).into_js_value())
+                .collect()
+        })
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+/// Converts the single part computed by a linear algebra routine into a Ndarray.
+fn into_ndarray(result: Result<Vec<NdarrayBase<f64>>, String>) -> Result<Ndarray, JsValue> {
+    result
+        .map(|mut parts| Ndarray(NdarrayUnion::F64(parts.remove(0))))
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+/// LU decomposition with partial pivoting of a (stack of) square matrices.
+///
+/// Returns an Array `[P, L, U]` with a permutation matrix `P`, a lower triangular matrix `L` with unit diagonal and an upper triangular matrix `U`, so that `A = P L U`.
+#[wasm_bindgen]
+pub fn lu(a: &Ndarray) -> Result<js_sys::Array, JsValue> {
+    let a = to_f64(a);
+    let n = square_shape(&a.shape)
+        .map_err(|err| JsValue::from_str(&err))?
+        .1;
+    let parts = map_matrices(&a, &[&[n, n], &[n, n], &[n, n]], |matrix, _, _| {
+        let lu = Lu::new(matrix, n);
+        let mut p = vec![0.0; n * n];
+        let mut l = identity(n);
+        let mut u = vec![0.0; n * n];
+        for i in 0..n {
+            p[lu.perm[i] * n + i] = 1.0;
+            for j in 0..n {
+                if i > j {
+                    l[i * n + j] = lu.lu[i * n + j];
+                } else {
+                    u[i * n + j] = lu.lu[i * n + j];
+                }
+            }
+        }
+        Ok(vec![p, l, u])
+    });
+    Ok(into_js_values(parts)?.into_iter().collect())
+}
+
+/// Solves the linear system `A x = b` for a (stack of) square matrices `A`. `b` is a vector or a (stack of) matrices.
+///
+/// Throws an error if a matrix is singular.
+#[wasm_bindgen]
+pub fn solve(a: &Ndarray, b: &Ndarray) -> Result<Ndarray, JsValue> {
+    solve_base(&to_f64(a), &to_f64(b))
+        .map(|x| Ndarray(NdarrayUnion::F64(x)))
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+/// Determinant of a (stack of) square matrices. Returns a number for a single matrix, otherwise a Ndarray.
+#[wasm_bindgen]
+pub fn det(a: &Ndarray) -> Result<JsValue, JsValue> {
+    let a = to_f64(a);
+    square_shape(&a.shape).map_err(|err| JsValue::from_str(&err))?;
+    let parts = map_matrices(&a, &[&[]], |matrix, n, _| {
+        Ok(vec![vec![Lu::new(matrix, n).det()]])
+    });
+    Ok(into_js_values(parts)?.remove(0))
+}
+
+/// Sign and natural logarithm of the absolute value of the determinant of a (stack of) square matrices.
+///
+/// Returns an Array `[sign, logabsdet]`. Singular matrices have sign `0` and logabsdet `-Infinity`. Unlike the determinant itself, the logarithm doesn't overflow for large matrices.
+#[wasm_bindgen]
+pub fn slogdet(a: &Ndarray) -> Result<js_sys::Array, JsValue> {
+    let a = to_f64(a);
+    square_shape(&a.shape).map_err(|err| JsValue::from_str(&err))?;
+    let parts = map_matrices(&a, &[&[], &[]], |matrix, n, _| {
+        let (sign, logdet) = Lu::new(matrix, n).slogdet();
+        Ok(vec![vec![sign], vec![logdet]])
+    });
+    Ok(into_js_values(parts)?.into_iter().collect())
+}
+
+/// Inverse of a (stack of) square matrices.
+///
+/// Throws an error if a matrix is singular.
+#[wasm_bindgen]
+pub fn inv(a: &Ndarray) -> Result<Ndarray, JsValue> {
+    let a = to_f64(a);
+    let n = square_shape(&a.shape)
+        .map_err(|err| JsValue::from_str(&err))?
+        .1;
+    into_ndarray(map_matrices(&a, &[&[n, n]], |matrix, _, _| {
+        let lu = Lu::new(matrix, n);
+        if lu.is_singular() {
+            Err(String::from("Matrix is singular."))
+        } else {
+            Ok(vec![lu.solve(&identity(n), n)])
+        }
+    }))
+}
@@ -0,0 +1,255 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// QR decomposition of a matrix with Householder reflections, optionally with column pivoting.
+pub struct Qr {
+    /// R on and above the diagonal and the Householder vectors below the diagonal in row-major order. The first entry of every Householder vector is an implicit one.
+    pub qr: Vec<f64>,
+    /// Scaling factors of the Householder reflections `H = I - tau v v^T`.
+    pub tau: Vec<f64>,
+    /// Column `j` of `Q R` is column `perm[j]` of the decomposed matrix.
+    pub perm: Vec<usize>,
+    pub m: usize,
+    pub n: usize,
+}
+
+impl Qr {
+    /// Decomposes a m x n matrix stored in row-major order.
+    ///
+    /// With `pivoting`, the remaining column with the largest norm is eliminated next, so the absolute values on the diagonal of R are non-increasing and reveal the numerical rank.
+    pub fn new(a: &[f64], m: usize, n: usize, pivoting: bool) -> Qr {
+        let mut qr = a.to_vec();
+        let mut tau = vec![0.0; m.min(n)];
+        let mut perm = (0..n).collect::<Vec<usize>>();
+        for k in 0..m.min(n) {
+            if pivoting {
+                let norm = |j: usize| (k..m).map(|i| qr[i * n + j] * qr[i * n + j]).sum::<f64>();
+                let p = (k..n).fold(k, |p, j| if norm(j) > norm(p) { j } else { p });
+                if p != k {
+                    for i in 0..m {
+                        qr.swap(i * n + k, i * n + p);
+                    }
+                    perm.swap(k, p);
+                }
+            }
+            let norm = (k..m)
+                .map(|i| qr[i * n + k] * qr[i * n + k])
+                .sum::<f64>()
+                .sqrt();
+            if norm == 0.0 {
+                continue;
+            }
+            let x0 = qr[k * n + k];
+            let beta = if x0 > 0.0 { -norm } else { norm };
+            tau[k] = (beta - x0) / beta;
+            for i in k + 1..m {
+                qr[i * n + k] /= x0 - beta;
+            }
+            qr[k * n + k] = beta;
+            for j in k + 1..n {
+                let w = qr[k * n + j]
+                    + (k + 1..m)
+                        .map(|i| qr[i * n + k] * qr[i * n + j])
+                        .sum::<f64>();
+                qr[k * n + j] -= tau[k] * w;
+                for i in k + 1..m {
+                    qr[i * n + j] -= tau[k] * w * qr[i * n + k];
+                }
+            }
+        }
+        Qr {
+            qr,
+            tau,
+            perm,
+            m,
+            n,
+        }
+    }
+
+    /// Applies the reflection `k` to a m x `cols` matrix `b` stored in row-major order.
+    fn reflect(&self, k: usize, b: &mut [f64], cols: usize) {
+        let (m, n) = (self.m, self.n);
+        for j in 0..cols {
+            let w = b[k * cols + j]
+                + (k + 1..m)
+                    .map(|i| self.qr[i * n + k] * b[i * cols + j])
+                    .sum::<f64>();
+            b[k * cols + j] -= self.tau[k] * w;
+            for i in k + 1..m {
+                b[i * cols + j] -= self.tau[k] * w * self.qr[i * n + k];
+            }
+        }
+    }
+
+    /// Multiplies a m x `cols` matrix stored in row-major order with `Q^T` in place.
+    pub fn apply_qt(&self, b: &mut [f64], cols: usize) {
+        for k in 0..self.tau.len() {
+            self.reflect(k, b, cols);
+        }
+    }
+
+    /// Multiplies a m x `cols` matrix stored in row-major order with `Q` in place.
+    pub fn apply_q(&self, b: &mut [f64], cols: usize) {
+        for k in (0..self.tau.len()).rev() {
+            self.reflect(k, b, cols);
+        }
+    }
+
+    /// Returns the first `cols` columns of Q in row-major order.
+    pub fn q(&self, cols: usize) -> Vec<f64> {
+        let mut q = vec![0.0; self.m * cols];
+        for i in 0..self.m.min(cols) {
+            q[i * cols + i] = 1.0;
+        }
+        self.apply_q(&mut q, cols);
+        q
+    }
+
+    /// Returns the first `rows` rows of R in row-major order.
+    pub fn r(&self, rows: usize) -> Vec<f64> {
+        let n = self.n;
+        (0..rows * n)
+            .map(|x| {
+                let (i, j) = (x / n, x % n);
+                if i <= j && i < self.m {
+                    self.qr[i * n + j]
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+}
+
+/// Result of a least-squares solution.
+pub struct Lstsq {
+    /// Solution with `n` rows and `k` columns in row-major order.
+    pub x: Vec<f64>,
+    /// Squared euclidian norms of the residuals of every column. Only computed if the matrix has full column rank and more rows than columns, otherwise empty.
+    pub residuals: Vec<f64>,
+    pub rank: usize,
+}
+
+/// Computes the minimum-norm solution `x` that minimizes `|a x - b|` for a m x n matrix `a` and a m x k right-hand side `b`.
+///
+/// Uses a complete orthogonal decomposition: a QR decomposition with column pivoting determines the rank, and for rank-deficient or underdetermined systems the remaining upper trapezoidal factor is decomposed again. Singular values below `rcond` times the largest one are treated as zero.
+pub fn lstsq_base(a: &[f64], m: usize, n: usize, b: &[f64], k: usize, rcond: f64) -> Lstsq {
+    let qr = Qr::new(a, m, n, true);
+    let tol = rcond * qr.qr.first().map_or(0.0, |x| x.abs());
+    let rank = (0..m.min(n))
+        .take_while(|&i| qr.qr[i * n + i].abs() > tol)
+        .count();
+    let mut c = b.to_vec();
+    qr.apply_qt(&mut c, k);
+    let mut y = vec![0.0; n * k];
+    if rank == n {
+        // back substitution with R
+        for i in (0..n).rev() {
+            for j in 0..k {
+                let sum = (i + 1..n)
+                    .map(|p| qr.qr[i * n + p] * y[p * k + j])
+                    .sum::<f64>();
+                y[i * k + j] = (c[i * k + j] - sum) / qr.qr[i * n + i];
+            }
+        }
+    } else if rank > 0 {
+        // the leading rows of R are decomposed as R1 = T^T Z^T with the QR decomposition of their transpose
+        let r1t = (0..n * rank)
+            .map(|x| {
+                let (i, j) = (x / rank, x % rank);
+                if j <= i {
+                    qr.qr[j * n + i]
+                } else {
+                    0.0
+                }
+            })
+            .collect::<Vec<f64>>();
+        let z = Qr::new(&r1t, n, rank, false);
+        // forward substitution with the lower triangular T^T
+        for i in 0..rank {
+            for j in 0..k {
+                let sum = (0..i)
+                    .map(|p| z.qr[p * rank + i] * y[p * k + j])
+                    .sum::<f64>();
+                y[i * k + j] = (c[i * k + j] - sum) / z.qr[i * rank + i];
+            }
+        }
+        z.apply_q(&mut y, k);
+    }
+    let mut x = vec![0.0; n * k];
+    for (i, &p) in qr.perm.iter().enumerate() {
+        x[p * k..(p + 1) * k].copy_from_slice(&y[i * k..(i + 1) * k]);
+    }
+    let residuals = if rank == n && m > n {
+        (0..k)
+            .map(|j| (n..m).map(|i| c[i * k + j] * c[i * k + j]).sum())
+            .collect()
+    } else {
+        vec![]
+    };
+    Lstsq { x, residuals, rank }
+}
+
+/// QR decomposition of a (stack of) matrices with Householder reflections.
+///
+/// Returns an Array `[Q, R]` with orthonormal columns in `Q` and an upper triangular `R`, so that `A = Q R`. With mode `"reduced"` (default) Q has shape m x k and R has shape k x n with k = min(m, n), with mode `"complete"` Q has shape m x m and R has shape m x n.
+#[wasm_bindgen]
+pub fn qr(a: &Ndarray, mode: Option<String>) -> Result<js_sys::Array, JsValue> {
+    let a = to_f64(a);
+    let (_, m, n) = matrix_shape(&a.shape).map_err(|err| JsValue::from_str(&err))?;
+    let rows = match mode.as_deref() {
+        None | Some("reduced") => m.min(n),
+        Some("complete") => m,
+        Some(mode) => {
+            return Err(JsValue::from_str(&format!(
+                "Unknown mode '{}', expected 'reduced' or 'complete'.",
+                mode
+            )))
+        }
+    };
+    let parts = map_matrices(&a, &[&[m, rows], &[rows, n]], |matrix, m, n| {
+        let qr = Qr::new(matrix, m, n, false);
+        Ok(vec![qr.q(rows), qr.r(rows)])
+    });
+    Ok(into_js_values(parts)?.into_iter().collect())
+}
+
+/// Least-squares solution of the linear system `A x = b` for a matrix `A` and a vector or matrix `b`.
+///
+/// Returns an Array `[x, residuals, rank]`. For rank-deficient and underdetermined systems `x` is the solution with minimal norm. `residuals` contains the squared norms of the residuals for every column of `b` if `A` has full column rank and more rows than columns, otherwise it is empty. Singular values smaller than `rcond` times the largest singular value are treated as zero, by default `rcond` is the machine precision times max(m, n).
+#[wasm_bindgen]
+pub fn lstsq(a: &Ndarray, b: &Ndarray, rcond: Option<f64>) -> Result<js_sys::Array, JsValue> {
+    let a = to_f64(a);
+    let b = to_f64(b);
+    if a.shape.len() != 2 {
+        return Err(JsValue::from_str(
+            "Matrix of lstsq must be two-dimensional.",
+        ));
+    }
+    let (m, n) = (a.shape[0], a.shape[1]);
+    let vector = b.shape.len() == 1;
+    if b.shape.is_empty() || b.shape.len() > 2 || b.shape[0] != m {
+        return Err(JsValue::from_str(&format!(
+            "Right-hand side of shape {:?} doesn't fit matrix of shape {:?}.",
+            b.shape, a.shape
+        )));
+    }
+    let k = if vector { 1 } else { b.shape[1] };
+    let rcond = rcond.unwrap_or(f64::EPSILON * m.max(n) as f64);
+    let result = lstsq_base(&a.to_vec(), m, n, &b.to_vec(), k, rcond);
+    let x_shape = if vector { vec![n] } else { vec![n, k] };
+    let residuals_len = result.residuals.len();
+    Ok(vec![
+        JsValue::from(Ndarray(NdarrayUnion::F64(NdarrayBase::from_vec(
+            result.x, x_shape,
+        )))),
+        JsValue::from(Ndarray(NdarrayUnion::F64(NdarrayBase::from_vec(
+            result.residuals,
+            vec![residuals_len],
+        )))),
+        JsValue::from_f64(result.rank as f64),
+    ]
+    .into_iter()
+    .collect())
+}
@@ -0,0 +1,350 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Maximum number of Jacobi sweeps before `svd_base` gives up.
+const MAX_SWEEPS: usize = 100;
+
+/// Singular value decomposition `a = U diag(s) V^T` of a m x n matrix.
+pub struct Svd {
+    /// Left singular vectors in the columns of a m x m or m x min(m, n) matrix in row-major order.
+    pub u: Vec<f64>,
+    /// Singular values in descending order.
+    pub s: Vec<f64>,
+    /// Right singular vectors in the columns of a n x n or n x min(m, n) matrix in row-major order.
+    pub v: Vec<f64>,
+}
+
+/// Returns the transpose of a m x n matrix stored in row-major order.
+fn transpose(a: &[f64], m: usize, n: usize) -> Vec<f64> {
+    (0..m * n).map(|x| a[(x % m) * n + x / m]).collect()
+}
+
+/// Computes the singular value decomposition of a m x n matrix with m >= n using one-sided Jacobi rotations.
+///
+/// The rotations orthogonalize the columns of `a`, whose lengths are then the singular values. Returns `U` with n columns.
+fn jacobi_svd(a: &[f64], m: usize, n: usize) -> Result<Svd, String> {
+    let mut u = a.to_vec();
+    let mut v = identity(n);
+    // columns that shrank to rounding errors of the norm of `a` can't be orthogonalized any further
+    let negligible = f64::EPSILON * f64::EPSILON * a.iter().map(|x| x * x).sum::<f64>();
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let (mut alpha, mut beta, mut gamma) = (0.0, 0.0, 0.0);
+                for i in 0..m {
+                    alpha += u[i * n + p] * u[i * n + p];
+                    beta += u[i * n + q] * u[i * n + q];
+                    gamma += u[i * n + p] * u[i * n + q];
+                }
+                if gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt()
+                    || alpha.min(beta) <= negligible
+                {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                let c = 1.0 / (1.0 + t * t).sqrt();
+                let s = c * t;
+                for (matrix, rows) in [(&mut u, m), (&mut v, n)] {
+                    for i in 0..rows {
+                        let (x, y) = (matrix[i * n + p], matrix[i * n + q]);
+                        matrix[i * n + p] = c * x - s * y;
+                        matrix[i * n + q] = s * x + c * y;
+                    }
+                }
+            }
+        }
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(String::from("SVD did not converge."));
+    }
+    let norms = (0..n)
+        .map(|j| {
+            (0..m)
+                .map(|i| u[i * n + j] * u[i * n + j])
+                .sum::<f64>()
+                .sqrt()
+        })
+        .collect::<Vec<f64>>();
+    let mut order = (0..n).collect::<Vec<usize>>();
+    order.sort_by(|&i, &j| norms[j].partial_cmp(&norms[i]).unwrap());
+    let s = order.iter().map(|&j| norms[j]).collect();
+    let u = (0..m * n)
+        .map(|x| {
+            let j = order[x % n];
+            if norms[j] == 0.0 {
+                0.0
+            } else {
+                u[(x / n) * n + j] / norms[j]
+            }
+        })
+        .collect();
+    let v = (0..n * n).map(|x| v[(x / n) * n + order[x % n]]).collect();
+    Ok(Svd { u, s, v })
+}
+
+/// Orthogonalizes `x` against the orthonormal vectors in `basis` with Gram-Schmidt, repeated once for numerical stability, and returns its remaining norm.
+fn orthogonalize(x: &mut [f64], basis: &[Vec<f64>]) -> f64 {
+    for _ in 0..2 {
+        for b in basis {
+            let dot = b.iter().zip(x.iter()).map(|(b, x)| b * x).sum::<f64>();
+            for (x, b) in x.iter_mut().zip(b.iter()) {
+                *x -= dot * b;
+            }
+        }
+    }
+    x.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+/// Replaces the columns of the m x `cols` matrix `u` that belong to singular values at the level of rounding errors and appends columns until there are `target` columns, so that all columns are orthonormal.
+///
+/// The kept columns are orthogonalized again, the others are filled with unit vectors orthogonalized against the columns before them. A unit vector that keeps at least half of its length is taken, otherwise the one that keeps the most.
+fn complete_basis(
+    u: &[f64],
+    s: &[f64],
+    m: usize,
+    cols: usize,
+    target: usize,
+) -> Result<Vec<f64>, String> {
+    let tol = s.first().cloned().unwrap_or(0.0) * m.max(cols) as f64 * f64::EPSILON;
+    let unit = |e: usize| {
+        (0..m)
+            .map(|i| if i == e { 1.0 } else { 0.0 })
+            .collect::<Vec<f64>>()
+    };
+    let mut columns: Vec<Vec<f64>> = Vec::with_capacity(target);
+    // unit vectors before `next` were already tried
+    let mut next = 0;
+    for j in 0..target {
+        if j < cols && s[j] > tol {
+            let mut x = (0..m).map(|i| u[i * cols + j]).collect::<Vec<f64>>();
+            let norm = orthogonalize(&mut x, &columns);
+            if norm > 0.5 {
+                columns.push(x.into_iter().map(|x| x / norm).collect());
+                continue;
+            }
+        }
+        let mut candidate = None;
+        while next < m {
+            let mut x = unit(next);
+            next += 1;
+            let norm = orthogonalize(&mut x, &columns);
+            if norm > 0.5 {
+                candidate = Some((x, norm));
+                break;
+            }
+        }
+        if candidate.is_none() {
+            candidate = (0..m)
+                .map(|e| {
+                    let mut x = unit(e);
+                    let norm = orthogonalize(&mut x, &columns);
+                    (x, norm)
+                })
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap());
+        }
+        match candidate {
+            Some((x, norm)) if norm > f64::EPSILON => {
+                columns.push(x.into_iter().map(|x| x / norm).collect())
+            }
+            _ => return Err(String::from("SVD could not complete the basis.")),
+        }
+    }
+    Ok((0..m * target)
+        .map(|x| columns[x % target][x / target])
+        .collect())
+}
+
+/// Computes the singular value decomposition of a m x n matrix stored in row-major order.
+///
+/// With `full`, `U` and `V` are complete orthogonal matrices, otherwise they have min(m, n) columns.
+pub fn svd_base(a: &[f64], m: usize, n: usize, full: bool) -> Result<Svd, String> {
+    if m < n {
+        // the decomposition of the transpose has the singular vectors swapped
+        let svd = svd_base(&transpose(a, m, n), n, m, full)?;
+        return Ok(Svd {
+            u: svd.v,
+            s: svd.s,
+            v: svd.u,
+        });
+    }
+    let svd = jacobi_svd(a, m, n)?;
+    let cols = if full { m } else { n };
+    Ok(Svd {
+        u: complete_basis(&svd.u, &svd.s, m, n, cols)?,
+        s: svd.s,
+        v: svd.v,
+    })
+}
+
+/// Returns the singular values of a m x n matrix stored in row-major order in descending order.
+pub fn singular_values(a: &[f64], m: usize, n: usize) -> Result<Vec<f64>, String> {
+    if m < n {
+        jacobi_svd(&transpose(a, m, n), n, m).map(|svd| svd.s)
+    } else {
+        jacobi_svd(a, m, n).map(|svd| svd.s)
+    }
+}
+
+/// Matrix norms supported by `cond`.
+#[derive(Clone, Copy, PartialEq)]
+enum Norm {
+    Two,
+    MinusTwo,
+    One,
+    MinusOne,
+    Infinity,
+    MinusInfinity,
+    Frobenius,
+    Nuclear,
+}
+
+impl Norm {
+    /// Parses the javascript order argument of `cond`. `undefined` and `null` select the 2-norm.
+    fn parse(p: &JsValue) -> Result<Norm, JsValue> {
+        if p.is_undefined() || p.is_null() {
+            return Ok(Norm::Two);
+        }
+        match (p.as_f64(), p.as_string().as_deref()) {
+            (Some(2.0), _) => Ok(Norm::Two),
+            (Some(-2.0), _) => Ok(Norm::MinusTwo),
+            (Some(1.0), _) => Ok(Norm::One),
+            (Some(-1.0), _) => Ok(Norm::MinusOne),
+            (Some(x), _) if x == f64::INFINITY => Ok(Norm::Infinity),
+            (Some(x), _) if x == f64::NEG_INFINITY => Ok(Norm::MinusInfinity),
+            (_, Some("fro")) => Ok(Norm::Frobenius),
+            (_, Some("nuc")) => Ok(Norm::Nuclear),
+            _ => Err(JsValue::from_str(
+                "Order must be one of 2, -2, 1, -1, Infinity, -Infinity, 'fro' or 'nuc'.",
+            )),
+        }
+    }
+
+    /// Computes the norm of a n x n matrix stored in row-major order. The orders 2 and -2 are the largest and smallest singular value.
+    fn compute(self, a: &[f64], n: usize) -> Result<f64, String> {
+        let column = |j: usize| (0..n).map(|i| a[i * n + j].abs()).sum::<f64>();
+        let row = |i: usize| a[i * n..(i + 1) * n].iter().map(|x| x.abs()).sum::<f64>();
+        Ok(match self {
+            Norm::One => (0..n).map(column).fold(0.0, f64::max),
+            Norm::MinusOne => (0..n).map(column).fold(f64::INFINITY, f64::min),
+            Norm::Infinity => (0..n).map(row).fold(0.0, f64::max),
+            Norm::MinusInfinity => (0..n).map(row).fold(f64::INFINITY, f64::min),
+            Norm::Frobenius => a.iter().map(|x| x * x).sum::<f64>().sqrt(),
+            Norm::Nuclear => singular_values(a, n, n)?.iter().sum(),
+            Norm::Two => singular_values(a, n, n)?.first().cloned().unwrap_or(0.0),
+            Norm::MinusTwo => singular_values(a, n, n)?.last().cloned().unwrap_or(0.0),
+        })
+    }
+}
+
+/// Singular value decomposition of a (stack of) matrices.
+///
+/// Returns an Array `[U, S, Vh]` with the singular values `S` in descending order, so that `A = U diag(S) Vh`. If `fullMatrices` is set (default) `U` and `Vh` are square with m and n rows, otherwise they have shapes m x k and k x n with k = min(m, n).
+#[wasm_bindgen]
+pub fn svd(a: &Ndarray, full_matrices: Option<bool>) -> Result<js_sys::Array, JsValue> {
+    let a = to_f64(a);
+    let (_, m, n) = matrix_shape(&a.shape).map_err(|err| JsValue::from_str(&err))?;
+    let full = full_matrices.unwrap_or(true);
+    let k = m.min(n);
+    let (u_cols, vh_rows) = if full { (m, n) } else { (k, k) };
+    let parts = map_matrices(&a, &[&[m, u_cols], &[k], &[vh_rows, n]], |matrix, m, n| {
+        let svd = svd_base(matrix, m, n, full)?;
+        let v_cols = svd.v.len() / n.max(1);
+        // Vh is the transpose of V, restricted to the requested number of rows
+        let vh = (0..vh_rows * n)
+            .map(|x| svd.v[(x % n) * v_cols + x / n])
+            .collect();
+        Ok(vec![svd.u, svd.s, vh])
+    });
+    Ok(into_js_values(parts)?.into_iter().collect())
+}
+
+/// Moore-Penrose pseudo-inverse of a (stack of) matrices, computed from the singular value decomposition.
+///
+/// Singular values smaller than `rcond` times the largest singular value are treated as zero, by default `rcond` is 1e-15.
+#[wasm_bindgen]
+pub fn pinv(a: &Ndarray, rcond: Option<f64>) -> Result<Ndarray, JsValue> {
+    let a = to_f64(a);
+    let (_, m, n) = matrix_shape(&a.shape).map_err(|err| JsValue::from_str(&err))?;
+    let rcond = rcond.unwrap_or(1e-15);
+    into_ndarray(map_matrices(&a, &[&[n, m]], |matrix, m, n| {
+        let svd = svd_base(matrix, m, n, false)?;
+        let k = m.min(n);
+        let cutoff = rcond * svd.s.first().cloned().unwrap_or(0.0);
+        let v_cols = svd.v.len() / n.max(1);
+        let mut result = vec![0.0; n * m];
+        for (p, &s) in svd.s.iter().enumerate().filter(|(_, &s)| s > cutoff) {
+            for i in 0..n {
+                let x = svd.v[i * v_cols + p] / s;
+                for j in 0..m {
+                    result[i * m + j] += x * svd.u[j * k + p];
+                }
+            }
+        }
+        Ok(vec![result])
+    }))
+}
+
+/// Rank of a (stack of) matrices, the number of singular values larger than `tol`.
+///
+/// By default `tol` is the largest singular value times max(m, n) times the machine precision. Returns a number for a single matrix, otherwise a Ndarray.
+#[wasm_bindgen(js_name = matrixRank)]
+pub fn matrix_rank(a: &Ndarray, tol: Option<f64>) -> Result<JsValue, JsValue> {
+    let a = to_f64(a);
+    matrix_shape(&a.shape).map_err(|err| JsValue::from_str(&err))?;
+    let parts = map_matrices(&a, &[&[]], |matrix, m, n| {
+        let s = singular_values(matrix, m, n)?;
+        let tol = tol
+            .unwrap_or_else(|| s.first().cloned().unwrap_or(0.0) * m.max(n) as f64 * f64::EPSILON);
+        Ok(vec![vec![s.iter().filter(|&&s| s > tol).count() as f64]])
+    });
+    Ok(into_js_values(parts)?.remove(0))
+}
+
+/// Condition number of a (stack of) matrices in the norm of order `p`.
+///
+/// The default order 2 (and -2) is the ratio of the largest and smallest singular value and works for rectangular matrices. The orders 1, -1, Infinity, -Infinity, `'fro'` and `'nuc'` compute the norm of the matrix times the norm of its inverse and require square matrices, singular matrices have the condition number Infinity. Returns a number for a single matrix, otherwise a Ndarray.
+#[wasm_bindgen]
+pub fn cond(a: &Ndarray, p: JsValue) -> Result<JsValue, JsValue> {
+    let a = to_f64(a);
+    let norm = Norm::parse(&p)?;
+    let result = match norm {
+        Norm::Two | Norm::MinusTwo => {
+            matrix_shape(&a.shape).map_err(|err| JsValue::from_str(&err))?;
+            map_matrices(&a, &[&[]], |matrix, m, n| {
+                let s = singular_values(matrix, m, n)?;
+                let (largest, smallest) = match (s.first(), s.last()) {
+                    (Some(&largest), Some(&smallest)) => (largest, smallest),
+                    _ => return Err(String::from("Condition number of an empty matrix.")),
+                };
+                Ok(vec![vec![if norm == Norm::Two {
+                    largest / smallest
+                } else {
+                    smallest / largest
+                }]])
+            })
+        }
+        _ => {
+            square_shape(&a.shape).map_err(|err| JsValue::from_str(&err))?;
+            map_matrices(&a, &[&[]], |matrix, n, _| {
+                let lu = Lu::new(matrix, n);
+                if lu.is_singular() {
+                    return Ok(vec![vec![f64::INFINITY]]);
+                }
+                let inverse = lu.solve(&identity(n), n);
+                Ok(vec![vec![
+                    norm.compute(matrix, n)? * norm.compute(&inverse, n)?,
+                ]])
+            })
+        }
+    };
+    Ok(into_js_values(result)?.remove(0))
+}
@@ -0,0 +1,403 @@
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen::convert::TryFromJsValue;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+use crate::js_interop;
+use crate::linalg::cholesky::*;
+use crate::linalg::eig::*;
+use crate::linalg::qr::*;
+use crate::linalg::svd::*;
+use crate::linalg::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn matrix(input: Vec<f64>, rows: isize, cols: isize) -> Ndarray {
+    let mut ndarray = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
+    ndarray
+        .reshape(&js_interop::vec_isize_into_array(vec![rows, cols]))
+        .unwrap();
+    ndarray
+}
+
+fn to_ndarray(value: JsValue) -> Ndarray {
+    Ndarray::try_from_js_value(value).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_solve() {
+    let a = matrix(vec![2.0, 1.0, 1.0, 4.0, -6.0, 0.0, -2.0, 7.0, 2.0], 3, 3);
+    let b = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        vec![5.0, -2.0, 9.0],
+    )));
+    let x = solve(&a, &b).unwrap();
+    let expected = [1.0, 1.0, 2.0];
+    for i in 0..3 {
+        let value = x
+            .get(js_interop::vec_isize_into_array(vec![i as isize]))
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        assert!((value - expected[i]).abs() < 1e-12);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_det_inv() {
+    let a = matrix(vec![4.0, 7.0, 2.0, 6.0], 2, 2);
+    assert!((det(&a).unwrap().as_f64().unwrap() - 10.0).abs() < 1e-12);
+    let inverse = inv(&a).unwrap();
+    assert!(
+        (inverse
+            .get(js_interop::vec_isize_into_array(vec![0, 1]))
+            .unwrap()
+            .as_f64()
+            .unwrap()
+            + 0.7)
+            .abs()
+            < 1e-12
+    );
+    let parts = slogdet(&a).unwrap();
+    assert_eq!(parts.get(0).as_f64().unwrap(), 1.0);
+    assert!((parts.get(1).as_f64().unwrap() - 10f64.ln()).abs() < 1e-12);
+}
+
+#[wasm_bindgen_test]
+fn test_singular_and_non_square() {
+    let singular = matrix(vec![1.0, 2.0, 2.0, 4.0], 2, 2);
+    assert!(inv(&singular).is_err());
+    assert_eq!(det(&singular).unwrap().as_f64().unwrap(), 0.0);
+    let rectangular = matrix(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2, 3);
+    assert!(det(&rectangular).is_err());
+    assert!(lu(&rectangular).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_qr() {
+    let a = matrix(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 3, 2);
+    let parts = qr(&a, None).unwrap();
+    let q = to_ndarray(parts.get(0));
+    let r = to_ndarray(parts.get(1));
+    assert_eq!(q.shape(), &vec![3, 2]);
+    assert_eq!(r.shape(), &vec![2, 2]);
+    let product = to_ndarray(q.matmul(&r).unwrap());
+    for i in 0..3 {
+        for j in 0..2 {
+            let index = vec![i as isize, j as isize];
+            let value = product
+                .get(js_interop::vec_isize_into_array(index.clone()))
+                .unwrap()
+                .as_f64()
+                .unwrap();
+            let expected = a
+                .get(js_interop::vec_isize_into_array(index))
+                .unwrap()
+                .as_f64()
+                .unwrap();
+            assert!((value - expected).abs() < 1e-12);
+        }
+    }
+    let parts = qr(&a, Some(String::from("complete"))).unwrap();
+    assert_eq!(to_ndarray(parts.get(0)).shape(), &vec![3, 3]);
+    assert!(qr(&a, Some(String::from("raw"))).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_lstsq() {
+    // fit of a line through four points
+    let a = matrix(vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0], 4, 2);
+    let b = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        vec![1.0, 3.0, 5.0, 7.5],
+    )));
+    let parts = lstsq(&a, &b, None).unwrap();
+    let x = to_ndarray(parts.get(0));
+    let expected = [0.9, 2.15];
+    for i in 0..2 {
+        let value = x
+            .get(js_interop::vec_isize_into_array(vec![i as isize]))
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        assert!((value - expected[i]).abs() < 1e-12);
+    }
+    let residuals = to_ndarray(parts.get(1));
+    assert!(
+        (residuals
+            .get(js_interop::vec_isize_into_array(vec![0]))
+            .unwrap()
+            .as_f64()
+            .unwrap()
+            - 0.075)
+            .abs()
+            < 1e-12
+    );
+    assert_eq!(parts.get(2).as_f64().unwrap(), 2.0);
+
+    // underdetermined system with the minimum-norm solution [1, 1, 1]
+    let a = matrix(vec![1.0, 1.0, 1.0], 1, 3);
+    let b = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        vec![3.0],
+    )));
+    let parts = lstsq(&a, &b, None).unwrap();
+    let x = to_ndarray(parts.get(0));
+    for i in 0..3 {
+        let value = x
+            .get(js_interop::vec_isize_into_array(vec![i]))
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        assert!((value - 1.0).abs() < 1e-12);
+    }
+    assert_eq!(to_ndarray(parts.get(1)).shape(), &vec![0]);
+    assert_eq!(parts.get(2).as_f64().unwrap(), 1.0);
+}
+
+#[wasm_bindgen_test]
+fn test_cholesky() {
+    let a = matrix(
+        vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+        3,
+        3,
+    );
+    let l = cholesky(&a, None).unwrap();
+    let expected = [2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0];
+    for i in 0..3 {
+        for j in 0..3 {
+            let value = l
+                .get(js_interop::vec_isize_into_array(vec![i, j]))
+                .unwrap()
+                .as_f64()
+                .unwrap();
+            assert!((value - expected[(i * 3 + j) as usize]).abs() < 1e-12);
+        }
+    }
+    let u = cholesky(&a, Some(true)).unwrap();
+    let b = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        vec![1.0, 2.0, 3.0],
+    )));
+    let x = cho_solve(&u, &b, Some(true)).unwrap();
+    let expected = [28.583333333333332, -7.666666666666666, 1.3333333333333333];
+    for i in 0..3 {
+        let value = x
+            .get(js_interop::vec_isize_into_array(vec![i as isize]))
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        assert!((value - expected[i]).abs() < 1e-9);
+    }
+    let indefinite = matrix(vec![1.0, 2.0, 2.0, 1.0], 2, 2);
+    assert!(cholesky(&indefinite, None).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_eigh() {
+    let a = matrix(vec![2.0, 1.0, 1.0, 2.0], 2, 2);
+    let parts = eigh(&a).unwrap();
+    let w = to_ndarray(parts.get(0));
+    let v = to_ndarray(parts.get(1));
+    let expected = [1.0, 3.0];
+    for i in 0..2 {
+        let value = w
+            .get(js_interop::vec_isize_into_array(vec![i as isize]))
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        assert!((value - expected[i]).abs() < 1e-12);
+    }
+    let x = v
+        .get(js_interop::vec_isize_into_array(vec![0, 1]))
+        .unwrap()
+        .as_f64()
+        .unwrap();
+    let y = v
+        .get(js_interop::vec_isize_into_array(vec![1, 1]))
+        .unwrap()
+        .as_f64()
+        .unwrap();
+    assert!((x.abs() - 0.5f64.sqrt()).abs() < 1e-12);
+    assert!((x - y).abs() < 1e-12);
+}
+
+/// Checks that the eigenpairs returned by `eig` satisfy `A v = lambda v` and that the eigenvectors have unit length.
+fn assert_eigenpairs(a: &Ndarray, n: usize) {
+    let values = |x: JsValue| js_sys::Float64Array::from(to_ndarray(x).to_typed_array()).to_vec();
+    let a_values = js_sys::Float64Array::from(a.to_typed_array()).to_vec();
+    let parts = eig(a).unwrap();
+    let (wr, wi, vr, vi) = (
+        values(parts.get(0)),
+        values(parts.get(1)),
+        values(parts.get(2)),
+        values(parts.get(3)),
+    );
+    // the residuals are compared relative to the largest entry, so that scaled matrices are checked too
+    let scale = a_values.iter().fold(0.0f64, |max, x| max.max(x.abs()));
+    for e in 0..n {
+        let length = (0..n)
+            .map(|i| vr[i * n + e].powi(2) + vi[i * n + e].powi(2))
+            .sum::<f64>();
+        assert!((length - 1.0).abs() < 1e-12);
+        for i in 0..n {
+            let re = (0..n)
+                .map(|j| a_values[i * n + j] * vr[j * n + e])
+                .sum::<f64>();
+            let im = (0..n)
+                .map(|j| a_values[i * n + j] * vi[j * n + e])
+                .sum::<f64>();
+            assert!((re - (wr[e] * vr[i * n + e] - wi[e] * vi[i * n + e])).abs() <= 1e-9 * scale);
+            assert!((im - (wr[e] * vi[i * n + e] + wi[e] * vr[i * n + e])).abs() <= 1e-9 * scale);
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_eig_zero_and_scaled() {
+    let values = |x: JsValue| js_sys::Float64Array::from(to_ndarray(x).to_typed_array()).to_vec();
+    // every vector is an eigenvector of the zero matrix, the unit vectors are returned
+    let parts = eig(&matrix(vec![0.0; 4], 2, 2)).unwrap();
+    assert_eq!(values(parts.get(0)), vec![0.0, 0.0]);
+    assert_eq!(values(parts.get(2)), vec![1.0, 0.0, 0.0, 1.0]);
+    let parts = eig(&matrix(vec![0.0], 1, 1)).unwrap();
+    assert_eq!(values(parts.get(2)), vec![1.0]);
+    assert_eigenpairs(&matrix(vec![0.0; 9], 3, 3), 3);
+
+    let data = vec![
+        1.0, 2.0, 3.0, 4.0, 0.0, 1.0, 5.0, 2.0, 1.0, 0.0, 2.0, 1.0, 3.0, 1.0, 0.0, 1.0,
+    ];
+    let expected = values(eigvals(&matrix(data.clone(), 4, 4)).unwrap().get(0));
+    for &factor in [1e-300, 1e-200, 1e200, 1e300].iter() {
+        let a = matrix(data.iter().map(|x| x * factor).collect(), 4, 4);
+        assert_eigenpairs(&a, 4);
+        let wr = values(eigvals(&a).unwrap().get(0));
+        for (x, y) in wr.iter().zip(expected.iter()) {
+            assert!((x / factor - y).abs() < 1e-9);
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_eig_complex() {
+    // rotation by 90 degrees has the eigenvalues i and -i
+    let a = matrix(vec![0.0, -1.0, 1.0, 0.0], 2, 2);
+    let parts = eigvals(&a).unwrap();
+    let wr = to_ndarray(parts.get(0));
+    let wi = to_ndarray(parts.get(1));
+    let expected = [1.0, -1.0];
+    for i in 0..2 {
+        let index = js_interop::vec_isize_into_array(vec![i as isize]);
+        assert!(wr.get(index.clone()).unwrap().as_f64().unwrap().abs() < 1e-12);
+        assert!((wi.get(index).unwrap().as_f64().unwrap() - expected[i]).abs() < 1e-12);
+    }
+    let parts = eig(&a).unwrap();
+    assert_eq!(parts.length(), 4);
+    let vi = to_ndarray(parts.get(3));
+    assert_eq!(vi.shape(), &vec![2, 2]);
+    assert_eigenpairs(&a, 2);
+
+    // two real eigenvalues and a complex conjugate pair
+    let a = matrix(
+        vec![
+            1.0, 2.0, 3.0, 4.0, 0.0, 1.0, 5.0, 2.0, 1.0, 0.0, 2.0, 1.0, 3.0, 1.0, 0.0, 1.0,
+        ],
+        4,
+        4,
+    );
+    let parts = eigvals(&a).unwrap();
+    let wi = js_sys::Float64Array::from(to_ndarray(parts.get(1)).to_typed_array()).to_vec();
+    assert_eq!(wi.iter().filter(|&&x| x != 0.0).count(), 2);
+    assert_eigenpairs(&a, 4);
+}
+
+#[wasm_bindgen_test]
+fn test_svd() {
+    let a = matrix(vec![3.0, 0.0, 0.0, 0.0, -4.0, 0.0], 2, 3);
+    let parts = svd(&a, None).unwrap();
+    assert_eq!(to_ndarray(parts.get(0)).shape(), &vec![2, 2]);
+    assert_eq!(to_ndarray(parts.get(2)).shape(), &vec![3, 3]);
+    let s = to_ndarray(parts.get(1));
+    let expected = [4.0, 3.0];
+    for i in 0..2 {
+        let value = s
+            .get(js_interop::vec_isize_into_array(vec![i as isize]))
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        assert!((value - expected[i]).abs() < 1e-12);
+    }
+    let parts = svd(&a, Some(false)).unwrap();
+    assert_eq!(to_ndarray(parts.get(2)).shape(), &vec![2, 3]);
+    assert!((cond(&a, JsValue::UNDEFINED).unwrap().as_f64().unwrap() - 4.0 / 3.0).abs() < 1e-12);
+}
+
+#[wasm_bindgen_test]
+fn test_svd_rank_deficient_full() {
+    // the second and fourth columns of this wide matrix are equal
+    let (m, n) = (5, 7);
+    let data = vec![
+        -2.0, 2.0, 3.0, 2.0, 1.0, -2.0, 1.0, 2.0, -3.0, -3.0, -3.0, 2.0, 1.0, 3.0, 2.0, -3.0, 2.0,
+        -3.0, 3.0, 0.0, 1.0, 3.0, -2.0, -3.0, -2.0, -3.0, 0.0, 0.0, 0.0, -3.0, -2.0, -3.0, -1.0,
+        -1.0, -1.0,
+    ];
+    let parts = svd(&matrix(data.clone(), m as isize, n as isize), Some(true)).unwrap();
+    let values =
+        |i: u32| js_sys::Float64Array::from(to_ndarray(parts.get(i)).to_typed_array()).to_vec();
+    let (u, s, vh) = (values(0), values(1), values(2));
+    assert_eq!((u.len(), s.len(), vh.len()), (m * m, m, n * n));
+    // U and Vh are orthogonal
+    for (q, k) in [(&u, m), (&vh, n)] {
+        for i in 0..k {
+            for j in 0..k {
+                let dot = (0..k).map(|r| q[i * k + r] * q[j * k + r]).sum::<f64>();
+                assert!((dot - if i == j { 1.0 } else { 0.0 }).abs() < 1e-12);
+            }
+        }
+    }
+    for i in 0..m {
+        for j in 0..n {
+            let x = (0..m)
+                .map(|r| u[i * m + r] * s[r] * vh[r * n + j])
+                .sum::<f64>();
+            assert!((x - data[i * n + j]).abs() < 1e-12);
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_pinv_rank() {
+    let a = matrix(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 3, 2);
+    let inverse = pinv(&a, None).unwrap();
+    assert_eq!(inverse.shape(), &vec![2, 3]);
+    let expected = [
+        -4.0 / 3.0,
+        -1.0 / 3.0,
+        2.0 / 3.0,
+        13.0 / 12.0,
+        1.0 / 3.0,
+        -5.0 / 12.0,
+    ];
+    for i in 0..2 {
+        for j in 0..3 {
+            let value = inverse
+                .get(js_interop::vec_isize_into_array(vec![i, j]))
+                .unwrap()
+                .as_f64()
+                .unwrap();
+            assert!((value - expected[(i * 3 + j) as usize]).abs() < 1e-12);
+        }
+    }
+    assert_eq!(matrix_rank(&a, None).unwrap().as_f64().unwrap(), 2.0);
+    let deficient = matrix(vec![1.0, 2.0, 2.0, 4.0], 2, 2);
+    assert_eq!(
+        matrix_rank(&deficient, None).unwrap().as_f64().unwrap(),
+        1.0
+    );
+    assert_eq!(
+        cond(&deficient, JsValue::from_f64(1.0))
+            .unwrap()
+            .as_f64()
+            .unwrap(),
+        f64::INFINITY
+    );
+}
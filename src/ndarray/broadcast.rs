@@ -0,0 +1,125 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Computes the shape that results from broadcasting the given shapes against each other.
+///
+/// The shapes are aligned at their last axis. Axes are compatible if they are equal or have length one, missing leading axes are treated as having length one.
+pub fn broadcast_shapes(shapes: &[&[usize]]) -> Result<Vec<usize>, String> {
+    let ndim = shapes.iter().map(|x| x.len()).max().unwrap_or(0);
+    let mut result = vec![1; ndim];
+    for shape in shapes {
+        for (len, x) in result[ndim - shape.len()..].iter_mut().zip(shape.iter()) {
+            if *len == 1 {
+                *len = *x;
+            } else if *x != 1 && *x != *len {
+                return Err(format!(
+                    "Shapes {:?} could not be broadcast together.",
+                    shapes
+                ));
+            }
+        }
+    }
+    Ok(result)
+}
+
+impl<T> NdarrayBase<T> {
+    /// Creates a view of the array with the given shape that references the same memory.
+    ///
+    /// New leading axes and axes of length one are repeated by giving them a stride of zero. For `Format::Slice` the view is turned into `Format::Slices`, because the offset of a repeated axis can't be expressed with a zero stride.
+    pub fn broadcast_to(&self, shape: &[usize]) -> Result<NdarrayBase<T>, String> {
+        if broadcast_shapes(&[&self.shape, shape]).ok().as_deref() != Some(shape) {
+            return Err(format!(
+                "Array of shape {:?} could not be broadcast to shape {:?}.",
+                self.shape, shape
+            ));
+        }
+        let new_axes = shape.len() - self.shape.len();
+        // an axis is repeated if it is new or has length one while the target axis doesn't
+        let repeated = |i: usize| i < new_axes || (self.shape[i - new_axes] == 1 && shape[i] != 1);
+        // repeated axes of index-list views keep their stride to reach the selected index
+        let strides = (0..shape.len())
+            .map(|i| match self.format {
+                Format::None if repeated(i) => 0,
+                _ if i < new_axes => 0,
+                _ => self.strides[i - new_axes],
+            })
+            .collect();
+        let format = match &self.format {
+            Format::None => Format::None,
+            Format::Slice(offset) => Format::Slices(
+                (0..shape.len())
+                    .map(|i| {
+                        if i < new_axes {
+                            vec![0; shape[i]]
+                        } else if repeated(i) {
+                            vec![offset[i - new_axes]; shape[i]]
+                        } else {
+                            (offset[i - new_axes]..offset[i - new_axes] + shape[i]).collect()
+                        }
+                    })
+                    .collect(),
+            ),
+            Format::Slices(slices) => Format::Slices(
+                (0..shape.len())
+                    .map(|i| {
+                        if i < new_axes {
+                            vec![0; shape[i]]
+                        } else if repeated(i) {
+                            vec![slices[i - new_axes][0]; shape[i]]
+                        } else {
+                            slices[i - new_axes].clone()
+                        }
+                    })
+                    .collect(),
+            ),
+        };
+        Ok(NdarrayBase {
+            data: self.data.clone(),
+            shape: shape.to_vec(),
+            strides,
+            offset: self.offset,
+            format,
+        })
+    }
+}
+
+/// Broadcasts two arrays against each other and returns views of both with the common shape.
+pub fn broadcast_pair<A, B>(
+    a: &NdarrayBase<A>,
+    b: &NdarrayBase<B>,
+) -> Result<(NdarrayBase<A>, NdarrayBase<B>), String> {
+    let shape = broadcast_shapes(&[&a.shape, &b.shape])?;
+    Ok((a.broadcast_to(&shape)?, b.broadcast_to(&shape)?))
+}
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Creates a view of the Ndarray with the given shape without copying the data. Leading axes and axes of length one are repeated.
+    #[wasm_bindgen(js_name = broadcastTo)]
+    pub fn broadcast_to(&self, shape: &js_sys::Array) -> Result<Ndarray, JsValue> {
+        let shape = js_interop::into_shape(shape)?;
+        let result = dispatch!(&self.0, ndarray => ndarray
+            .broadcast_to(&shape)
+            .map(NdarrayBase::into_union));
+        result.map(Ndarray).map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Computes the shape that results from broadcasting the shapes given as a Javascript Array of Arrays.
+    #[wasm_bindgen(js_name = broadcastShapes)]
+    pub fn broadcast_shapes(shapes: &js_sys::Array) -> Result<js_sys::Array, JsValue> {
+        let shapes = shapes
+            .iter()
+            .map(|x| js_interop::into_shape(&js_sys::Array::from(&x)))
+            .collect::<Result<Vec<Vec<usize>>, JsValue>>()?;
+        let shapes = shapes
+            .iter()
+            .map(|x| x.as_slice())
+            .collect::<Vec<&[usize]>>();
+        broadcast_shapes(&shapes)
+            .map(|shape| {
+                js_interop::vec_isize_into_array(shape.into_iter().map(|x| x as isize).collect())
+            })
+            .map_err(|err| JsValue::from_str(&err))
+    }
+}
@@ -0,0 +1,207 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Complex number with parts of type `F`.
+///
+/// The parts are stored next to each other, so the data of a complex Ndarray consists of interleaved real and imaginary parts. Complex numbers are ordered lexicographically by their real and then by their imaginary part.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Complex<F> {
+    pub re: F,
+    pub im: F,
+}
+
+impl<F> Complex<F> {
+    pub fn new(re: F, im: F) -> Complex<F> {
+        Complex { re, im }
+    }
+}
+
+macro_rules! impl_complex {
+    ($f:ty, $variant:ident, $array:ty) => {
+        impl Complex<$f> {
+            pub fn conj(self) -> Complex<$f> {
+                Complex::new(self.re, -self.im)
+            }
+            /// Magnitude of the complex number, computed without intermediate overflow.
+            pub fn abs(self) -> $f {
+                self.re.hypot(self.im)
+            }
+            /// Phase of the complex number in radians in the interval `[-pi, pi]`.
+            pub fn arg(self) -> $f {
+                self.im.atan2(self.re)
+            }
+            fn exp(self) -> Complex<$f> {
+                let r = self.re.exp();
+                Complex::new(r * self.im.cos(), r * self.im.sin())
+            }
+            /// Principal value of the natural logarithm.
+            fn ln(self) -> Complex<$f> {
+                Complex::new(self.abs().ln(), self.arg())
+            }
+        }
+
+        impl Arithmetic for Complex<$f> {
+            const ZERO: Complex<$f> = Complex { re: 0.0, im: 0.0 };
+            const ONE: Complex<$f> = Complex { re: 1.0, im: 0.0 };
+            fn to_f64(self) -> f64 {
+                self.re as f64
+            }
+            fn from_f64(x: f64) -> Complex<$f> {
+                Complex::new(x as $f, 0.0)
+            }
+            fn to_i128(self) -> i128 {
+                self.re as i128
+            }
+            fn from_i128(x: i128) -> Complex<$f> {
+                Complex::new(x as $f, 0.0)
+            }
+            fn to_complex(self) -> Complex<f64> {
+                Complex::new(self.re as f64, self.im as f64)
+            }
+            fn from_complex(x: Complex<f64>) -> Complex<$f> {
+                Complex::new(x.re as $f, x.im as $f)
+            }
+            fn add(self, other: Complex<$f>) -> Complex<$f> {
+                Complex::new(self.re + other.re, self.im + other.im)
+            }
+            fn sub(self, other: Complex<$f>) -> Complex<$f> {
+                Complex::new(self.re - other.re, self.im - other.im)
+            }
+            fn mul(self, other: Complex<$f>) -> Complex<$f> {
+                Complex::new(
+                    self.re * other.re - self.im * other.im,
+                    self.re * other.im + self.im * other.re,
+                )
+            }
+            fn div(self, other: Complex<$f>) -> Complex<$f> {
+                let norm = other.re * other.re + other.im * other.im;
+                Complex::new(
+                    (self.re * other.re + self.im * other.im) / norm,
+                    (self.im * other.re - self.re * other.im) / norm,
+                )
+            }
+            /// Principal value of `self^other`. Zero to the power of zero is one.
+            fn pow(self, other: Complex<$f>) -> Complex<$f> {
+                if self == Self::ZERO {
+                    if other == Self::ZERO {
+                        Self::ONE
+                    } else {
+                        Self::ZERO
+                    }
+                } else {
+                    other.mul(self.ln()).exp()
+                }
+            }
+            /// The remainder isn't defined for complex numbers, so `Ndarray::binary_op` rejects it before this is called.
+            fn rem(self, _: Complex<$f>) -> Complex<$f> {
+                Complex::new(<$f>::NAN, <$f>::NAN)
+            }
+        }
+
+        impl Element for Complex<$f> {
+            const DTYPE: DType = DType::$variant;
+            fn wrap(ndarray: NdarrayBase<Complex<$f>>) -> NdarrayUnion {
+                NdarrayUnion::$variant(ndarray)
+            }
+            /// Complex numbers are converted into objects with the properties `re` and `im`.
+            fn to_js(self) -> JsValue {
+                let object = js_sys::Object::new();
+                js_sys::Reflect::set(&object, &JsValue::from_str("re"), &JsValue::from(self.re))
+                    .unwrap();
+                js_sys::Reflect::set(&object, &JsValue::from_str("im"), &JsValue::from(self.im))
+                    .unwrap();
+                JsValue::from(object)
+            }
+            /// The real and imaginary parts are interleaved, so the typed array has twice as many elements as the array.
+            fn to_typed_array(data: &[Complex<$f>]) -> JsValue {
+                let parts = data
+                    .iter()
+                    .flat_map(|x| vec![x.re, x.im])
+                    .collect::<Vec<$f>>();
+                JsValue::from(<$array>::from(&parts[..]))
+            }
+        }
+    };
+}
+
+impl_complex!(f32, C64, js_sys::Float32Array);
+impl_complex!(f64, C128, js_sys::Float64Array);
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Creates a complex Ndarray from Ndarrays of the real and imaginary parts. The shapes of both Ndarrays are broadcast against each other.
+    ///
+    /// The result is `complex64` if both parts fit into `float32`, otherwise `complex128`.
+    pub fn complex(re: &Ndarray, im: &Ndarray) -> Result<Ndarray, JsValue> {
+        let dtype = DType::promote(re.0.dtype(), im.0.dtype());
+        let result = if DType::promote(dtype, DType::F32) == DType::F32 {
+            ops::zip_with(&re.0.convert::<f32>(), &im.0.convert::<f32>(), Complex::new)
+                .map(NdarrayBase::into_union)
+        } else {
+            ops::zip_with(&re.0.convert::<f64>(), &im.0.convert::<f64>(), Complex::new)
+                .map(NdarrayBase::into_union)
+        };
+        result.map(Ndarray).map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Returns the real parts of a complex Ndarray. Other Ndarrays are returned unchanged.
+    pub fn real(&self) -> Ndarray {
+        match &self.0 {
+            NdarrayUnion::C64(ndarray) => Ndarray(ndarray.map(|x| x.re).into_union()),
+            NdarrayUnion::C128(ndarray) => Ndarray(ndarray.map(|x| x.re).into_union()),
+            union => Ndarray(dispatch!(union, ndarray => ndarray.clone().into_union())),
+        }
+    }
+
+    /// Returns the imaginary parts of a complex Ndarray. Other Ndarrays result in zeros of the same type.
+    pub fn imag(&self) -> Ndarray {
+        fn zeros<T: Arithmetic>(ndarray: &NdarrayBase<T>) -> NdarrayUnion {
+            ndarray.map(|_| T::ZERO).into_union()
+        }
+        match &self.0 {
+            NdarrayUnion::C64(ndarray) => Ndarray(ndarray.map(|x| x.im).into_union()),
+            NdarrayUnion::C128(ndarray) => Ndarray(ndarray.map(|x| x.im).into_union()),
+            union => Ndarray(dispatch!(union, ndarray => zeros(ndarray))),
+        }
+    }
+
+    /// Returns the complex conjugates of the elements. Other Ndarrays are returned unchanged.
+    pub fn conj(&self) -> Ndarray {
+        match &self.0 {
+            NdarrayUnion::C64(ndarray) => Ndarray(ndarray.map(|x| x.conj()).into_union()),
+            NdarrayUnion::C128(ndarray) => Ndarray(ndarray.map(|x| x.conj()).into_union()),
+            union => Ndarray(dispatch!(union, ndarray => ndarray.clone().into_union())),
+        }
+    }
+
+    /// Returns the absolute values of the elements. Complex Ndarrays result in the magnitudes of their elements as floating point numbers.
+    pub fn abs(&self) -> Ndarray {
+        fn abs<T: Arithmetic>(ndarray: &NdarrayBase<T>) -> NdarrayUnion {
+            ndarray
+                .map(|x| if x < T::ZERO { T::ZERO.sub(x) } else { x })
+                .into_union()
+        }
+        match &self.0 {
+            NdarrayUnion::C64(ndarray) => Ndarray(ndarray.map(|x| x.abs()).into_union()),
+            NdarrayUnion::C128(ndarray) => Ndarray(ndarray.map(|x| x.abs()).into_union()),
+            union => Ndarray(dispatch!(union, ndarray => abs(ndarray))),
+        }
+    }
+
+    /// Returns the phase angles of the elements in radians. Negative real numbers have the angle pi, all other real numbers zero.
+    ///
+    /// The result is `float32` for `complex64` Ndarrays and `float64` otherwise.
+    pub fn angle(&self) -> Ndarray {
+        match &self.0 {
+            NdarrayUnion::C64(ndarray) => Ndarray(ndarray.map(|x| x.arg()).into_union()),
+            union => Ndarray(
+                union
+                    .convert::<Complex<f64>>()
+                    .map(|x| x.arg())
+                    .into_union(),
+            ),
+        }
+    }
+}
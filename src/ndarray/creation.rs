@@ -0,0 +1,308 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Parses the optional javascript dtype argument of a creation routine, which defaults to `default`.
+pub fn parse_dtype(dtype: Option<String>, default: DType) -> Result<DType, JsValue> {
+    match dtype {
+        Some(name) => DType::parse(&name).map_err(|err| JsValue::from_str(&err)),
+        None => Ok(default),
+    }
+}
+
+/// Parses the optional number of elements of `linspace`, `logspace` and `geomspace`, which defaults to 50 and must be a non-negative integer.
+fn parse_num(num: Option<f64>) -> Result<usize, JsValue> {
+    js_interop::into_dim(num.unwrap_or(50.0))
+}
+
+impl Ndarray {
+    /// Creates a Ndarray of the given shape and type with every element set to `value`.
+    fn filled(shape: Vec<usize>, dtype: DType, value: f64) -> Ndarray {
+        let size = shape.iter().product::<usize>();
+        with_dtype!(dtype, T => Ndarray::new_rust(vec![T::from_f64(value); size], shape)).unwrap()
+    }
+
+    /// Creates a two-dimensional n x m Ndarray with ones on the `k`-th diagonal and zeros elsewhere.
+    fn eye_rust(n: usize, m: usize, k: isize, dtype: DType) -> Result<Ndarray, JsValue> {
+        let size = n
+            .checked_mul(m)
+            .ok_or_else(|| JsValue::from_str("Shape has too many elements."))?;
+        with_dtype!(dtype, T => {
+            let mut data = vec![T::ZERO; size];
+            for i in 0..n {
+                let j = i as isize + k;
+                if j >= 0 && (j as usize) < m {
+                    data[i * m + j as usize] = T::ONE;
+                }
+            }
+            Ndarray::new_rust(data, vec![n, m])
+        })
+        .map_err(|err| JsValue::from_str(&err))
+    }
+}
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Creates a Ndarray of the given shape filled with zeros. The type defaults to `float64`.
+    pub fn zeros(shape: &js_sys::Array, dtype: Option<String>) -> Result<Ndarray, JsValue> {
+        Ndarray::full(shape, 0.0, dtype)
+    }
+
+    /// Creates a Ndarray of the given shape filled with ones. The type defaults to `float64`.
+    pub fn ones(shape: &js_sys::Array, dtype: Option<String>) -> Result<Ndarray, JsValue> {
+        Ndarray::full(shape, 1.0, dtype)
+    }
+
+    /// Creates a Ndarray of the given shape with every element set to `value`, which is converted to the type. The type defaults to `float64`.
+    pub fn full(
+        shape: &js_sys::Array,
+        value: f64,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let shape = js_interop::into_shape(shape)?;
+        let dtype = parse_dtype(dtype, DType::F64)?;
+        Ok(Ndarray::filled(shape, dtype, value))
+    }
+
+    /// Creates a Ndarray of the given shape without meaningful values. The type defaults to `float64`.
+    ///
+    /// The elements are currently set to zero, but code shouldn't rely on that.
+    pub fn empty(shape: &js_sys::Array, dtype: Option<String>) -> Result<Ndarray, JsValue> {
+        Ndarray::full(shape, 0.0, dtype)
+    }
+
+    /// Creates a two-dimensional Ndarray with `n` rows and `m` columns that has ones on the `k`-th diagonal and zeros elsewhere.
+    ///
+    /// `n` and `m` must be non-negative integers. `m` defaults to `n` and `k` to the main diagonal. Positive `k` refer to diagonals above the main diagonal, negative `k` to diagonals below. The type defaults to `float64`.
+    pub fn eye(
+        n: f64,
+        m: Option<f64>,
+        k: Option<isize>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let n = js_interop::into_dim(n)?;
+        let m = match m {
+            Some(m) => js_interop::into_dim(m)?,
+            None => n,
+        };
+        let dtype = parse_dtype(dtype, DType::F64)?;
+        Ndarray::eye_rust(n, m, k.unwrap_or(0), dtype)
+    }
+
+    /// Creates the n x n identity matrix, where `n` must be a non-negative integer. The type defaults to `float64`.
+    pub fn identity(n: f64, dtype: Option<String>) -> Result<Ndarray, JsValue> {
+        let n = js_interop::into_dim(n)?;
+        let dtype = parse_dtype(dtype, DType::F64)?;
+        Ndarray::eye_rust(n, n, 0, dtype)
+    }
+
+    /// Creates a Ndarray filled with zeros that has the shape and, unless `dtype` is given, the type of `other`.
+    #[wasm_bindgen(js_name = zerosLike)]
+    pub fn zeros_like(other: &Ndarray, dtype: Option<String>) -> Result<Ndarray, JsValue> {
+        Ndarray::full_like(other, 0.0, dtype)
+    }
+
+    /// Creates a Ndarray filled with ones that has the shape and, unless `dtype` is given, the type of `other`.
+    #[wasm_bindgen(js_name = onesLike)]
+    pub fn ones_like(other: &Ndarray, dtype: Option<String>) -> Result<Ndarray, JsValue> {
+        Ndarray::full_like(other, 1.0, dtype)
+    }
+
+    /// Creates a Ndarray with every element set to `value` that has the shape and, unless `dtype` is given, the type of `other`.
+    #[wasm_bindgen(js_name = fullLike)]
+    pub fn full_like(
+        other: &Ndarray,
+        value: f64,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let dtype = parse_dtype(dtype, other.0.dtype())?;
+        Ok(Ndarray::filled(other.shape().clone(), dtype, value))
+    }
+}
+
+/// Returns `num` evenly spaced numbers over the interval from `start` to `stop`, which is closed if `endpoint` is set and half-open otherwise.
+pub fn linspace_base(start: f64, stop: f64, num: usize, endpoint: bool) -> Vec<f64> {
+    let intervals = if endpoint && num > 1 { num - 1 } else { num };
+    let step = (stop - start) / intervals as f64;
+    (0..num)
+        .map(|i| {
+            if endpoint && i + 1 == num && num > 1 {
+                stop
+            } else {
+                start + i as f64 * step
+            }
+        })
+        .collect()
+}
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Creates a one-dimensional Ndarray with the numbers from `start` (inclusive) to `stop` (exclusive) in increments of `step`, which defaults to one. If `stop` isn't given, the numbers range from zero to `start`. The bounds must be finite.
+    ///
+    /// The type defaults to `int32` if all arguments are integers that fit into an `i32` and to `float64` otherwise.
+    pub fn arange(
+        start: f64,
+        stop: Option<f64>,
+        step: Option<f64>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let (start, stop) = match stop {
+            Some(stop) => (start, stop),
+            None => (0.0, start),
+        };
+        let step = step.unwrap_or(1.0);
+        if step == 0.0 || !step.is_finite() {
+            return Err(JsValue::from_str(
+                "Step must be a finite number other than zero.",
+            ));
+        }
+        if !start.is_finite() || !stop.is_finite() {
+            return Err(JsValue::from_str("Start and stop must be finite numbers."));
+        }
+        let integral = [start, stop, step]
+            .iter()
+            .all(|&x| x.fract() == 0.0 && x >= i32::MIN as f64 && x <= i32::MAX as f64);
+        let dtype = parse_dtype(dtype, if integral { DType::I32 } else { DType::F64 })?;
+        let len = ((stop - start) / step).ceil().max(0.0);
+        if len > isize::MAX as f64 {
+            return Err(JsValue::from_str("Range has too many elements."));
+        }
+        let len = len as usize;
+        let data = (0..len).map(|i| start + i as f64 * step);
+        with_dtype!(dtype, T => Ndarray::new_rust(data.map(T::from_f64).collect(), vec![len]))
+            .map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Creates a one-dimensional Ndarray with `num` (default 50, a non-negative integer) evenly spaced numbers from `start` to `stop`. `stop` is excluded if `endpoint` is `false`. The type defaults to `float64`.
+    pub fn linspace(
+        start: f64,
+        stop: f64,
+        num: Option<f64>,
+        endpoint: Option<bool>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let data = linspace_base(start, stop, parse_num(num)?, endpoint.unwrap_or(true));
+        Ndarray::from_f64_data(data, dtype)
+    }
+
+    /// Creates a one-dimensional Ndarray with `num` (default 50) numbers that are evenly spaced on a log scale from `base^start` to `base^stop`. The base defaults to 10.
+    ///
+    /// `base^stop` is excluded if `endpoint` is `false`. The type defaults to `float64`.
+    pub fn logspace(
+        start: f64,
+        stop: f64,
+        num: Option<f64>,
+        endpoint: Option<bool>,
+        base: Option<f64>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let base = base.unwrap_or(10.0);
+        let data = linspace_base(start, stop, parse_num(num)?, endpoint.unwrap_or(true))
+            .into_iter()
+            .map(|x| base.powf(x))
+            .collect();
+        Ndarray::from_f64_data(data, dtype)
+    }
+
+    /// Creates a one-dimensional Ndarray with `num` (default 50) numbers from `start` to `stop` that form a geometric progression. `stop` is excluded if `endpoint` is `false`.
+    ///
+    /// `start` and `stop` must not be zero and must have the same sign. The type defaults to `float64`.
+    pub fn geomspace(
+        start: f64,
+        stop: f64,
+        num: Option<f64>,
+        endpoint: Option<bool>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        if start == 0.0 || stop == 0.0 || (start < 0.0) != (stop < 0.0) {
+            return Err(JsValue::from_str(
+                "Geomspace requires start and stop to be non-zero and to have the same sign.",
+            ));
+        }
+        let num = parse_num(num)?;
+        let endpoint = endpoint.unwrap_or(true);
+        let sign = start.signum();
+        let mut data = linspace_base(start.abs().log10(), stop.abs().log10(), num, endpoint)
+            .into_iter()
+            .map(|x| sign * 10f64.powf(x))
+            .collect::<Vec<f64>>();
+        // the ends are set exactly, so they don't suffer from rounding errors
+        if num > 0 {
+            data[0] = start;
+        }
+        if endpoint && num > 1 {
+            data[num - 1] = stop;
+        }
+        Ndarray::from_f64_data(data, dtype)
+    }
+
+    /// Creates coordinate grids from a Javascript Array of one-dimensional Ndarrays, one for each dimension of the grid. Returns an Array with one Ndarray per input that repeats its coordinates along the other axes.
+    ///
+    /// With the default `indexing` `"xy"` the first two axes of the grids are swapped, so that the first input varies along the columns like in plotting libraries. With `"ij"` the axes are in the order of the inputs. The grids reference the memory of copies of the inputs instead of repeating the coordinates in memory.
+    pub fn meshgrid(
+        arrays: &js_sys::Array,
+        indexing: Option<String>,
+    ) -> Result<js_sys::Array, JsValue> {
+        let swap = match indexing.as_deref() {
+            None | Some("xy") => arrays.length() > 1,
+            Some("ij") => false,
+            Some(_) => return Err(JsValue::from_str("Indexing must be \"xy\" or \"ij\".")),
+        };
+        let arrays = arrays
+            .iter()
+            .map(|x| Ndarray::borrow_js(&x))
+            .collect::<Result<Vec<Ndarray>, JsValue>>()?;
+        let mut shape = arrays
+            .iter()
+            .map(|x| x.shape().iter().product::<usize>())
+            .collect::<Vec<usize>>();
+        if swap {
+            shape.swap(0, 1);
+        }
+        arrays
+            .iter()
+            .enumerate()
+            .map(|(i, array)| {
+                let axis = match i {
+                    0 if swap => 1,
+                    1 if swap => 0,
+                    _ => i,
+                };
+                let mut axis_shape = vec![1; shape.len()];
+                axis_shape[axis] = shape[axis];
+                let grid = dispatch!(&array.0, ndarray => NdarrayBase::from_vec(ndarray.to_vec(), axis_shape)
+                    .broadcast_to(&shape)
+                    .map(NdarrayBase::into_union));
+                grid.map(|x| JsValue::from(Ndarray(x)))
+                    .map_err(|err| JsValue::from_str(&err))
+            })
+            .collect()
+    }
+}
+
+impl Ndarray {
+    /// Creates a one-dimensional Ndarray from floating point numbers, which are converted to `dtype` (default `float64`).
+    fn from_f64_data(data: Vec<f64>, dtype: Option<String>) -> Result<Ndarray, JsValue> {
+        let dtype = parse_dtype(dtype, DType::F64)?;
+        let len = data.len();
+        Ok(Ndarray::from_f64_vec(data, vec![len], dtype).unwrap())
+    }
+
+    /// Creates a Ndarray of the given shape and type from floating point numbers that are stored contigiously in row-major order.
+    pub fn from_f64_vec(
+        data: Vec<f64>,
+        shape: Vec<usize>,
+        dtype: DType,
+    ) -> Result<Ndarray, String> {
+        with_dtype!(dtype, T => Ndarray::new_rust(data.into_iter().map(T::from_f64).collect(), shape))
+    }
+
+    /// Creates a Ndarray of the given shape and type from integers that are stored contigiously in row-major order. Integers that don't fit into an integer type wrap around.
+    pub fn from_i128_vec(
+        data: Vec<i128>,
+        shape: Vec<usize>,
+        dtype: DType,
+    ) -> Result<Ndarray, String> {
+        with_dtype!(dtype, T => Ndarray::new_rust(data.into_iter().map(T::from_i128).collect(), shape))
+    }
+}
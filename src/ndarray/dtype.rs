@@ -0,0 +1,516 @@
+use std::any::Any;
+
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Applies `$body` to the NdarrayBase inside any variant of a NdarrayUnion, which is bound to `$ndarray`.
+///
+/// The body is instantiated once for every element type, so it can call generic functions. The union can be matched by value or by (mutable) reference.
+macro_rules! dispatch {
+    ($union:expr, $ndarray:ident => $body:expr) => {
+        match $union {
+            NdarrayUnion::Bool($ndarray) => $body,
+            NdarrayUnion::I8($ndarray) => $body,
+            NdarrayUnion::U8($ndarray) => $body,
+            NdarrayUnion::I16($ndarray) => $body,
+            NdarrayUnion::U16($ndarray) => $body,
+            NdarrayUnion::I32($ndarray) => $body,
+            NdarrayUnion::U32($ndarray) => $body,
+            NdarrayUnion::I64($ndarray) => $body,
+            NdarrayUnion::U64($ndarray) => $body,
+            NdarrayUnion::F16($ndarray) => $body,
+            NdarrayUnion::BF16($ndarray) => $body,
+            NdarrayUnion::F32($ndarray) => $body,
+            NdarrayUnion::F64($ndarray) => $body,
+            NdarrayUnion::C64($ndarray) => $body,
+            NdarrayUnion::C128($ndarray) => $body,
+        }
+    };
+}
+
+/// Applies `$body` to the NdarrayBaseMut inside any variant of a NdarrayUnionMut, which is bound to `$ndarray`.
+macro_rules! dispatch_mut {
+    ($union:expr, $ndarray:ident => $body:expr) => {
+        match $union {
+            NdarrayUnionMut::Bool($ndarray) => $body,
+            NdarrayUnionMut::I8($ndarray) => $body,
+            NdarrayUnionMut::U8($ndarray) => $body,
+            NdarrayUnionMut::I16($ndarray) => $body,
+            NdarrayUnionMut::U16($ndarray) => $body,
+            NdarrayUnionMut::I32($ndarray) => $body,
+            NdarrayUnionMut::U32($ndarray) => $body,
+            NdarrayUnionMut::I64($ndarray) => $body,
+            NdarrayUnionMut::U64($ndarray) => $body,
+            NdarrayUnionMut::F16($ndarray) => $body,
+            NdarrayUnionMut::BF16($ndarray) => $body,
+            NdarrayUnionMut::F32($ndarray) => $body,
+            NdarrayUnionMut::F64($ndarray) => $body,
+            NdarrayUnionMut::C64($ndarray) => $body,
+            NdarrayUnionMut::C128($ndarray) => $body,
+        }
+    };
+}
+
+/// Evaluates `$body` with `$t` as an alias of the element type that corresponds to the given `DType`.
+macro_rules! with_dtype {
+    ($dtype:expr, $t:ident => $body:expr) => {
+        match $dtype {
+            DType::Bool => {
+                type $t = bool;
+                $body
+            }
+            DType::I8 => {
+                type $t = i8;
+                $body
+            }
+            DType::U8 => {
+                type $t = u8;
+                $body
+            }
+            DType::I16 => {
+                type $t = i16;
+                $body
+            }
+            DType::U16 => {
+                type $t = u16;
+                $body
+            }
+            DType::I32 => {
+                type $t = i32;
+                $body
+            }
+            DType::U32 => {
+                type $t = u32;
+                $body
+            }
+            DType::I64 => {
+                type $t = i64;
+                $body
+            }
+            DType::U64 => {
+                type $t = u64;
+                $body
+            }
+            DType::F16 => {
+                type $t = Float16;
+                $body
+            }
+            DType::BF16 => {
+                type $t = BFloat16;
+                $body
+            }
+            DType::F32 => {
+                type $t = f32;
+                $body
+            }
+            DType::F64 => {
+                type $t = f64;
+                $body
+            }
+            DType::C64 => {
+                type $t = Complex<f32>;
+                $body
+            }
+            DType::C128 => {
+                type $t = Complex<f64>;
+                $body
+            }
+        }
+    };
+}
+
+/// Element types of a Ndarray.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DType {
+    Bool,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F16,
+    BF16,
+    F32,
+    F64,
+    C64,
+    C128,
+}
+
+/// All element types, in the order of the variants of NdarrayUnion.
+pub const ALL_DTYPES: [DType; 15] = [
+    DType::Bool,
+    DType::I8,
+    DType::U8,
+    DType::I16,
+    DType::U16,
+    DType::I32,
+    DType::U32,
+    DType::I64,
+    DType::U64,
+    DType::F16,
+    DType::BF16,
+    DType::F32,
+    DType::F64,
+    DType::C64,
+    DType::C128,
+];
+
+impl DType {
+    /// Returns the name of the type as reported to javascript, which follows the names of numpy.
+    pub fn name(self) -> &'static str {
+        match self {
+            DType::Bool => "bool",
+            DType::I8 => "int8",
+            DType::U8 => "uint8",
+            DType::I16 => "int16",
+            DType::U16 => "uint16",
+            DType::I32 => "int32",
+            DType::U32 => "uint32",
+            DType::I64 => "int64",
+            DType::U64 => "uint64",
+            DType::F16 => "float16",
+            DType::BF16 => "bfloat16",
+            DType::F32 => "float32",
+            DType::F64 => "float64",
+            DType::C64 => "complex64",
+            DType::C128 => "complex128",
+        }
+    }
+
+    /// Returns the type with the given name, see `DType::name`.
+    pub fn parse(name: &str) -> Result<DType, String> {
+        ALL_DTYPES
+            .iter()
+            .find(|dtype| dtype.name() == name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown dtype {}.", name))
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, DType::F16 | DType::BF16 | DType::F32 | DType::F64)
+    }
+
+    pub fn is_complex(self) -> bool {
+        matches!(self, DType::C64 | DType::C128)
+    }
+
+    pub fn is_signed(self) -> bool {
+        !matches!(
+            self,
+            DType::Bool | DType::U8 | DType::U16 | DType::U32 | DType::U64
+        )
+    }
+
+    /// Size of an element in bits.
+    pub fn bits(self) -> usize {
+        match self {
+            DType::Bool | DType::I8 | DType::U8 => 8,
+            DType::I16 | DType::U16 | DType::F16 | DType::BF16 => 16,
+            DType::I32 | DType::U32 | DType::F32 => 32,
+            DType::I64 | DType::U64 | DType::F64 | DType::C64 => 64,
+            DType::C128 => 128,
+        }
+    }
+
+    /// Returns the smallest and largest value of an integer or boolean type, or `None` for floating point and complex types.
+    pub fn integer_bounds(self) -> Option<(i128, i128)> {
+        let bits = self.bits() as u32;
+        match self {
+            _ if self.is_float() || self.is_complex() => None,
+            DType::Bool => Some((0, 1)),
+            _ if self.is_signed() => Some((-(1 << (bits - 1)), (1 << (bits - 1)) - 1)),
+            _ => Some((0, (1 << bits) - 1)),
+        }
+    }
+
+    /// Returns the smallest type that can represent the values of both types, which is the type of the result of a binary operation.
+    ///
+    /// Booleans promote to the type of the other operand. Signed and unsigned integers are combined into a signed type that is large enough for both operands, or `float64` if there is none, which is the case for `uint64`. `float16` and `bfloat16` hold integers of up to 8 bits exactly and `float32` integers of up to 16 bits, larger integers promote to the next type that holds them. `float16` and `bfloat16` are combined into `float32`. Complex types promote like the floating point type of their parts.
+    pub fn promote(a: DType, b: DType) -> DType {
+        if a == b || b == DType::Bool {
+            return a;
+        }
+        if a == DType::Bool {
+            return b;
+        }
+        if a.is_complex() || b.is_complex() {
+            let part = |x: DType| match x {
+                DType::C64 => DType::F32,
+                DType::C128 => DType::F64,
+                x => x,
+            };
+            return match DType::promote(part(a), part(b)) {
+                DType::F16 | DType::BF16 | DType::F32 => DType::C64,
+                _ => DType::C128,
+            };
+        }
+        let (large, small) = if a.bits() >= b.bits() { (a, b) } else { (b, a) };
+        match (a.is_float(), b.is_float()) {
+            (true, true) if large.bits() == small.bits() => DType::F32,
+            (true, true) => large,
+            (true, false) | (false, true) => {
+                let (float, int) = if a.is_float() { (a, b) } else { (b, a) };
+                let exact_bits = match float {
+                    DType::F16 | DType::BF16 => 8,
+                    DType::F32 => 16,
+                    _ => 64,
+                };
+                if int.bits() <= exact_bits {
+                    float
+                } else if int.bits() <= 16 {
+                    DType::F32
+                } else {
+                    DType::F64
+                }
+            }
+            (false, false) if large.is_signed() == small.is_signed() => large,
+            (false, false) if large.is_signed() && large.bits() > small.bits() => large,
+            (false, false) => match large.bits() {
+                8 => DType::I16,
+                16 => DType::I32,
+                32 => DType::I64,
+                _ => DType::F64,
+            },
+        }
+    }
+}
+
+/// Element type that can be stored in a NdarrayUnion.
+pub trait Element: Copy + 'static {
+    const DTYPE: DType;
+    /// Wraps an array with elements of this type into the corresponding variant of NdarrayUnion.
+    fn wrap(ndarray: NdarrayBase<Self>) -> NdarrayUnion;
+    /// Converts a single element into a javascript value, which is a boolean for booleans, a BigInt for 64-bit integers and a number otherwise.
+    fn to_js(self) -> JsValue;
+    /// Copies the elements into the javascript typed array of this type.
+    fn to_typed_array(data: &[Self]) -> JsValue;
+}
+
+macro_rules! impl_element {
+    ($t:ty, $variant:ident, $array:ty) => {
+        impl_element!($t, $variant, $array, |x: $t| JsValue::from_f64(x as f64));
+    };
+    ($t:ty, $variant:ident, $array:ty, $to_js:expr) => {
+        impl Element for $t {
+            const DTYPE: DType = DType::$variant;
+            fn wrap(ndarray: NdarrayBase<$t>) -> NdarrayUnion {
+                NdarrayUnion::$variant(ndarray)
+            }
+            fn to_js(self) -> JsValue {
+                ($to_js)(self)
+            }
+            fn to_typed_array(data: &[$t]) -> JsValue {
+                JsValue::from(<$array>::from(data))
+            }
+        }
+    };
+}
+
+impl Element for bool {
+    const DTYPE: DType = DType::Bool;
+    fn wrap(ndarray: NdarrayBase<bool>) -> NdarrayUnion {
+        NdarrayUnion::Bool(ndarray)
+    }
+    fn to_js(self) -> JsValue {
+        JsValue::from_bool(self)
+    }
+    /// Booleans are stored as one byte each, so they are copied into a Uint8Array of zeros and ones.
+    fn to_typed_array(data: &[bool]) -> JsValue {
+        JsValue::from(js_sys::Uint8Array::from(
+            &data.iter().map(|&x| x as u8).collect::<Vec<u8>>()[..],
+        ))
+    }
+}
+
+impl_element!(i8, I8, js_sys::Int8Array);
+impl_element!(u8, U8, js_sys::Uint8Array);
+impl_element!(i16, I16, js_sys::Int16Array);
+impl_element!(u16, U16, js_sys::Uint16Array);
+impl_element!(i32, I32, js_sys::Int32Array);
+impl_element!(u32, U32, js_sys::Uint32Array);
+impl_element!(i64, I64, js_sys::BigInt64Array, |x: i64| JsValue::from(
+    js_sys::BigInt::from(x)
+));
+impl_element!(u64, U64, js_sys::BigUint64Array, |x: u64| JsValue::from(
+    js_sys::BigInt::from(x)
+));
+impl_element!(f32, F32, js_sys::Float32Array);
+impl_element!(f64, F64, js_sys::Float64Array);
+
+impl<T: Element> NdarrayBase<T> {
+    /// Wraps the array into the variant of NdarrayUnion that corresponds to its element type.
+    pub fn into_union(self) -> NdarrayUnion {
+        T::wrap(self)
+    }
+}
+
+/// Converts a single element with the semantics of `NdarrayUnion::convert`.
+fn cast<S: Arithmetic, T: Arithmetic>(x: S) -> T {
+    if S::DTYPE.is_complex() || T::DTYPE.is_complex() {
+        T::from_complex(x.to_complex())
+    } else if S::DTYPE.is_float() || T::DTYPE.is_float() {
+        T::from_f64(x.to_f64())
+    } else {
+        T::from_i128(x.to_i128())
+    }
+}
+
+/// How floating point numbers are rounded when they are cast to an integer type.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Rounding {
+    /// Rounds towards zero.
+    Trunc,
+    /// Rounds to the nearest integer, ties to even.
+    Round,
+    Floor,
+    Ceil,
+}
+
+impl Rounding {
+    /// Parses the optional javascript argument, which defaults to `"trunc"`.
+    pub fn parse(rounding: Option<String>) -> Result<Rounding, String> {
+        match rounding.as_deref() {
+            None | Some("trunc") => Ok(Rounding::Trunc),
+            Some("round") => Ok(Rounding::Round),
+            Some("floor") => Ok(Rounding::Floor),
+            Some("ceil") => Ok(Rounding::Ceil),
+            Some(other) => Err(format!(
+                "Rounding must be \"trunc\", \"round\", \"floor\" or \"ceil\", got \"{}\".",
+                other
+            )),
+        }
+    }
+
+    fn apply(self, x: f64) -> f64 {
+        match self {
+            Rounding::Trunc => x.trunc(),
+            Rounding::Round => x.round_ties_even(),
+            Rounding::Floor => x.floor(),
+            Rounding::Ceil => x.ceil(),
+        }
+    }
+}
+
+/// What happens to values outside of the range of an integer type when they are cast to it.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Overflow {
+    /// Integers wrap around and floating point numbers saturate, like `as` casts in Rust.
+    Cast,
+    /// Values wrap around modulo the range of the type, like the conversions of javascript typed arrays. Infinity becomes zero.
+    Wrap,
+    /// Values are clamped to the range of the type.
+    Saturate,
+}
+
+impl Overflow {
+    /// Parses the optional javascript argument, which defaults to `Overflow::Cast`.
+    pub fn parse(overflow: Option<String>) -> Result<Overflow, String> {
+        match overflow.as_deref() {
+            None => Ok(Overflow::Cast),
+            Some("wrap") => Ok(Overflow::Wrap),
+            Some("saturate") => Ok(Overflow::Saturate),
+            Some(other) => Err(format!(
+                "Overflow must be \"wrap\" or \"saturate\", got \"{}\".",
+                other
+            )),
+        }
+    }
+}
+
+/// Converts a single element like `cast`, but applies `rounding` and `overflow` when the target is an integer type. NaN always becomes zero.
+fn cast_with<S: Arithmetic, T: Arithmetic>(x: S, rounding: Rounding, overflow: Overflow) -> T {
+    let target = T::DTYPE;
+    if target.is_float() || target.is_complex() || target == DType::Bool {
+        return cast(x);
+    }
+    if S::DTYPE.is_float() || S::DTYPE.is_complex() {
+        let x = rounding.apply(x.to_f64());
+        match overflow {
+            // `as` casts from floating point numbers already saturate
+            Overflow::Cast | Overflow::Saturate => T::from_f64(x),
+            // larger numbers are multiples of 2^64, which wrap around to zero
+            Overflow::Wrap if x.abs() < 2f64.powi(127) => T::from_i128(x as i128),
+            Overflow::Wrap => T::ZERO,
+        }
+    } else {
+        let x = x.to_i128();
+        match overflow {
+            Overflow::Saturate => {
+                let (min, max) = target.integer_bounds().unwrap();
+                T::from_i128(x.clamp(min, max))
+            }
+            Overflow::Cast | Overflow::Wrap => T::from_i128(x),
+        }
+    }
+}
+
+impl NdarrayUnion {
+    /// Returns the element type of the array.
+    pub fn dtype(&self) -> DType {
+        fn dtype_of<T: Element>(_: &NdarrayBase<T>) -> DType {
+            T::DTYPE
+        }
+        dispatch!(self, ndarray => dtype_of(ndarray))
+    }
+
+    /// Converts the elements to the type `T` with the semantics of `as` casts in Rust.
+    ///
+    /// If the elements already have type `T` the returned array shares the data with this array, otherwise the converted elements are stored contigiously. Conversions between integer types don't go through `f64`, so 64-bit integers keep their precision. Converting complex numbers to other types discards the imaginary part, except for booleans, which are `true` if either part isn't zero.
+    pub fn convert<T: Arithmetic>(&self) -> NdarrayBase<T> {
+        dispatch!(self, ndarray => match (ndarray as &dyn Any).downcast_ref::<NdarrayBase<T>>() {
+            Some(same) => same.clone(),
+            None => ndarray.map(cast),
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Name of the element type of the Ndarray, for example `"float64"` or `"uint8"`.
+    #[wasm_bindgen(getter)]
+    pub fn dtype(&self) -> String {
+        String::from(self.0.dtype().name())
+    }
+
+    /// Returns a copy of the Ndarray with the elements converted to the type with the given name, for example `"int32"` or `"float16"`.
+    ///
+    /// When floating point numbers are converted to integers they are rounded according to `rounding`, which is one of `"trunc"` (default), `"round"` (to the nearest integer, ties to even), `"floor"` and `"ceil"`. Values outside of the range of an integer type are handled according to `overflow`: by default integers wrap around and floating point numbers saturate, `"wrap"` wraps all values around like javascript typed arrays and `"saturate"` clamps all values to the range of the type. NaN becomes zero.
+    ///
+    /// Conversions to booleans test for values that aren't zero and conversions of complex numbers to real numeric types discard the imaginary part.
+    pub fn astype(
+        &self,
+        dtype: &str,
+        rounding: Option<String>,
+        overflow: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let error = |err: String| JsValue::from_str(&err);
+        let dtype = DType::parse(dtype).map_err(error)?;
+        let rounding = Rounding::parse(rounding).map_err(error)?;
+        let overflow = Overflow::parse(overflow).map_err(error)?;
+        Ok(Ndarray(
+            with_dtype!(dtype, T => dispatch!(&self.0, ndarray => ndarray
+            .map(|x| cast_with::<_, T>(x, rounding, overflow))
+            .into_union())),
+        ))
+    }
+
+    /// Returns the name of the type that results from a binary operation of Ndarrays with the element types `a` and `b`.
+    ///
+    /// Booleans promote to the other type, integers of mixed signedness to a signed integer type that holds both, and integers combined with floating point numbers to the smallest floating point type that holds both exactly. For example `int32` and `float64` result in `float64`, `uint8` and `int8` in `int16` and `int32` and `float32` in `float64`.
+    #[wasm_bindgen(js_name = promoteTypes)]
+    pub fn promote_types(a: &str, b: &str) -> Result<String, JsValue> {
+        let a = DType::parse(a).map_err(|err| JsValue::from_str(&err))?;
+        let b = DType::parse(b).map_err(|err| JsValue::from_str(&err))?;
+        Ok(String::from(DType::promote(a, b).name()))
+    }
+
+    /// Copies the elements of the Ndarray in row-major order into a new typed array of the matching type, for example a Float32Array for `float32`. Booleans are copied into a Uint8Array.
+    #[wasm_bindgen(js_name = toTypedArray)]
+    pub fn to_typed_array(&self) -> JsValue {
+        dispatch!(&self.0, ndarray => Element::to_typed_array(&ndarray.to_vec()))
+    }
+}
@@ -0,0 +1,186 @@
+use std::cmp::Ordering;
+
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Rounds a number to the nearest floating point number with `exponent` exponent bits and `mantissa` mantissa bits and returns its bits.
+///
+/// Ties are rounded to even, numbers that are too large become infinite and NaN stays NaN.
+fn encode(x: f64, exponent: u32, mantissa: u32) -> u16 {
+    let bits = x.to_bits();
+    let sign = ((bits >> 63) as u16) << 15;
+    let max_exponent = (1 << exponent) - 1;
+    let bias = (1 << (exponent - 1)) - 1;
+    let x_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let x_mantissa = bits & ((1 << 52) - 1);
+    if x_exponent == 0x7ff {
+        let nan = if x_mantissa != 0 {
+            1 << (mantissa - 1)
+        } else {
+            0
+        };
+        return sign | (max_exponent << mantissa) as u16 | nan;
+    }
+    if x_exponent == 0 {
+        // subnormal numbers of f64 are far too small for 16 bits
+        return sign;
+    }
+    let e = x_exponent - 1023 + bias;
+    if e >= max_exponent {
+        return sign | (max_exponent << mantissa) as u16;
+    }
+    // subnormal results lose additional bits of the mantissa
+    let shift = 52 - mantissa as i32 + if e <= 0 { 1 - e } else { 0 };
+    if shift >= 64 {
+        return sign;
+    }
+    let m = x_mantissa | (1 << 52);
+    let q = m >> shift;
+    let rem = m & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    // for normal numbers the implicit bit of `q` increments the exponent field by one
+    let mut result = if e > 0 {
+        (((e - 1) as u64) << mantissa) + q
+    } else {
+        q
+    };
+    if rem > half || (rem == half && q & 1 == 1) {
+        // a carry moves into the exponent, which is the correctly rounded result
+        result += 1;
+    }
+    sign | result as u16
+}
+
+/// Returns the number represented by the bits of a floating point number with `exponent` exponent bits and `mantissa` mantissa bits.
+fn decode(bits: u16, exponent: u32, mantissa: u32) -> f64 {
+    let sign = if bits >> 15 == 1 { -1.0 } else { 1.0 };
+    let max_exponent = (1 << exponent) - 1;
+    let bias = (1 << (exponent - 1)) - 1;
+    let e = ((bits >> mantissa) & max_exponent) as i32;
+    let m = (bits & ((1 << mantissa) - 1)) as f64;
+    if e == 0 {
+        sign * m * 2f64.powi(1 - bias - mantissa as i32)
+    } else if e == max_exponent as i32 {
+        if m == 0.0 {
+            sign * f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    } else {
+        sign * (m + (1 << mantissa) as f64) * 2f64.powi(e - bias - mantissa as i32)
+    }
+}
+
+macro_rules! impl_half {
+    ($t:ident, $variant:ident, $exponent:expr, $mantissa:expr) => {
+        impl $t {
+            pub fn from_f64(x: f64) -> $t {
+                $t(encode(x, $exponent, $mantissa))
+            }
+            pub fn to_f64(self) -> f64 {
+                decode(self.0, $exponent, $mantissa)
+            }
+            pub fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+        }
+
+        impl PartialEq for $t {
+            fn eq(&self, other: &$t) -> bool {
+                self.to_f64() == other.to_f64()
+            }
+        }
+
+        impl PartialOrd for $t {
+            fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                self.to_f64().partial_cmp(&other.to_f64())
+            }
+        }
+
+        /// Arithmetic is computed in `f32` and rounded back to 16 bits.
+        impl Arithmetic for $t {
+            const ZERO: $t = $t(0);
+            const ONE: $t = $t((((1 << ($exponent - 1)) - 1) << $mantissa) as u16);
+            fn to_f64(self) -> f64 {
+                $t::to_f64(self)
+            }
+            fn from_f64(x: f64) -> $t {
+                $t::from_f64(x)
+            }
+            fn to_i128(self) -> i128 {
+                $t::to_f64(self) as i128
+            }
+            fn from_i128(x: i128) -> $t {
+                $t::from_f64(x as f64)
+            }
+            fn add(self, other: $t) -> $t {
+                $t::from_f64((self.to_f32() + other.to_f32()) as f64)
+            }
+            fn sub(self, other: $t) -> $t {
+                $t::from_f64((self.to_f32() - other.to_f32()) as f64)
+            }
+            fn mul(self, other: $t) -> $t {
+                $t::from_f64((self.to_f32() * other.to_f32()) as f64)
+            }
+            fn div(self, other: $t) -> $t {
+                $t::from_f64((self.to_f32() / other.to_f32()) as f64)
+            }
+            fn pow(self, other: $t) -> $t {
+                $t::from_f64(Arithmetic::pow(self.to_f32(), other.to_f32()) as f64)
+            }
+            fn rem(self, other: $t) -> $t {
+                $t::from_f64(Arithmetic::rem(self.to_f32(), other.to_f32()) as f64)
+            }
+        }
+
+        /// Elements are exported as their bits in a Uint16Array.
+        impl Element for $t {
+            const DTYPE: DType = DType::$variant;
+            fn wrap(ndarray: NdarrayBase<$t>) -> NdarrayUnion {
+                NdarrayUnion::$variant(ndarray)
+            }
+            fn to_js(self) -> JsValue {
+                JsValue::from_f64(self.to_f64())
+            }
+            fn to_typed_array(data: &[$t]) -> JsValue {
+                JsValue::from(js_sys::Uint16Array::from(
+                    &data.iter().map(|x| x.0).collect::<Vec<u16>>()[..],
+                ))
+            }
+        }
+    };
+}
+
+/// IEEE 754 half-precision floating point number, stored as its bits.
+#[derive(Clone, Copy, Debug)]
+pub struct Float16(pub u16);
+
+/// Brain floating point number, which has the exponent range of `f32` with only 8 bits of precision, stored as its bits.
+#[derive(Clone, Copy, Debug)]
+pub struct BFloat16(pub u16);
+
+impl_half!(Float16, F16, 5, 10);
+impl_half!(BFloat16, BF16, 8, 7);
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Creates a one-dimensional `float16` or `bfloat16` Ndarray from the bits of its elements given in a Uint16Array.
+    ///
+    /// The data is kept in 16 bits and is converted to floating point numbers when it's accessed. `toTypedArray` returns the bits in the same format.
+    #[wasm_bindgen(js_name = fromUint16Bits)]
+    pub fn from_uint16_bits(bits: &js_sys::Uint16Array, dtype: &str) -> Result<Ndarray, JsValue> {
+        let bits = bits.to_vec();
+        match DType::parse(dtype).map_err(|err| JsValue::from_str(&err))? {
+            DType::F16 => Ok(Ndarray::from_typed_array(
+                bits.into_iter().map(Float16).collect(),
+            )),
+            DType::BF16 => Ok(Ndarray::from_typed_array(
+                bits.into_iter().map(BFloat16).collect(),
+            )),
+            _ => Err(JsValue::from_str(
+                "Only float16 and bfloat16 Ndarrays can be created from bits.",
+            )),
+        }
+    }
+}
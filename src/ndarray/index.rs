@@ -0,0 +1,150 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Item of a NumPy-style index string.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IndexItem {
+    /// Range `start:stop:step` with optional bounds, which keeps the axis.
+    Range(Option<isize>, Option<isize>, Option<isize>),
+    /// Single index, which removes the axis.
+    Index(isize),
+    /// `...`, which stands for all axes that aren't indexed explicitly.
+    Ellipsis,
+    /// `None` or `newaxis`, which inserts a new axis of length one.
+    NewAxis,
+}
+
+/// Parses a bound of a range or an index, which is empty if `optional` is set.
+fn parse_integer(input: &str, optional: bool) -> Result<Option<isize>, String> {
+    let input = input.trim();
+    if optional && input.is_empty() {
+        return Ok(None);
+    }
+    input
+        .parse::<isize>()
+        .map(Some)
+        .map_err(|_| format!("Invalid index \"{}\".", input))
+}
+
+/// Parses a comma separated NumPy-style index string like `"1:3, ::2, ..., None, -1"`. A trailing comma is allowed.
+pub fn parse_index(input: &str) -> Result<Vec<IndexItem>, String> {
+    if input.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let mut items = input.split(',').map(str::trim).collect::<Vec<&str>>();
+    if items.len() > 1 && items.last() == Some(&"") {
+        items.pop();
+    }
+    items
+        .into_iter()
+        .map(|item| match item {
+            "..." => Ok(IndexItem::Ellipsis),
+            "None" | "newaxis" | "np.newaxis" => Ok(IndexItem::NewAxis),
+            _ if item.contains(':') => {
+                let parts = item.split(':').collect::<Vec<&str>>();
+                if parts.len() > 3 {
+                    return Err(format!("Invalid range \"{}\".", item));
+                }
+                let part = |i: usize| match parts.get(i) {
+                    Some(part) => parse_integer(part, true),
+                    None => Ok(None),
+                };
+                Ok(IndexItem::Range(part(0)?, part(1)?, part(2)?))
+            }
+            _ => Ok(IndexItem::Index(parse_integer(item, false)?.unwrap())),
+        })
+        .collect()
+}
+
+impl<T> NdarrayBase<T> {
+    /// Creates the view selected by the items of an index.
+    ///
+    /// Ranges and indices apply to the axes from the front, an ellipsis stands for as many whole axes as needed and axes without an item are kept completely.
+    pub fn index(&self, items: &[IndexItem]) -> Result<NdarrayBase<T>, String> {
+        let ndim = self.shape.len();
+        let explicit = items
+            .iter()
+            .filter(|item| matches!(item, IndexItem::Range(..) | IndexItem::Index(_)))
+            .count();
+        if explicit > ndim {
+            return Err(format!(
+                "Too many indices for an array of dimension {}.",
+                ndim
+            ));
+        }
+        if items
+            .iter()
+            .filter(|&&item| item == IndexItem::Ellipsis)
+            .count()
+            > 1
+        {
+            return Err(String::from("An index can only have a single ellipsis."));
+        }
+        let mut ranges = Vec::with_capacity(ndim);
+        let mut removed = Vec::new();
+        let mut inserted = Vec::new();
+        // `axis` counts the axes of the array, `position` the axes of the view
+        let (mut axis, mut position) = (0, 0);
+        for item in items {
+            match *item {
+                IndexItem::Range(start, stop, step) => {
+                    ranges.push(normalize_range(start, stop, step, self.shape[axis])?);
+                    axis += 1;
+                    position += 1;
+                }
+                IndexItem::Index(index) => {
+                    let len = self.shape[axis];
+                    let normalized = if index < 0 {
+                        index + len as isize
+                    } else {
+                        index
+                    };
+                    if normalized < 0 || normalized >= len as isize {
+                        return Err(format!(
+                            "Index {} is out of bounds for axis {} with length {}.",
+                            index, axis, len
+                        ));
+                    }
+                    ranges.push((normalized as usize, 1, 1));
+                    removed.push(axis);
+                    axis += 1;
+                }
+                IndexItem::Ellipsis => {
+                    for _ in 0..ndim - explicit {
+                        ranges.push((0, self.shape[axis], 1));
+                        axis += 1;
+                        position += 1;
+                    }
+                }
+                IndexItem::NewAxis => {
+                    inserted.push(position);
+                    position += 1;
+                }
+            }
+        }
+        let mut view = self.slice_axes(&ranges);
+        for &axis in removed.iter().rev() {
+            view = view.remove_axis(axis);
+        }
+        // axes that follow the items are kept, so the positions of new axes are counted from the front
+        for &position in inserted.iter() {
+            view = view.insert_axis(position);
+        }
+        Ok(view)
+    }
+}
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Selects a view of the Ndarray with a NumPy-style index string, for example `"1:3, ::2, ..., None, -1"`.
+    ///
+    /// The comma separated items are ranges `start:stop:step` with the semantics of `slice`, integers that select a single index and remove the axis, an ellipsis `...` that stands for all axes that aren't indexed explicitly and `None` or `newaxis` that insert a new axis of length one. Axes after the last item are kept completely. Selecting a single element returns it as a number.
+    pub fn index(&self, index: &str) -> Result<JsValue, JsValue> {
+        let items = parse_index(index).map_err(|err| JsValue::from_str(&err))?;
+        let view =
+            dispatch!(&self.0, ndarray => ndarray.index(&items).map(NdarrayBase::into_union));
+        view.map(|x| Ndarray(x).into_js_value())
+            .map_err(|err| JsValue::from_str(&err))
+    }
+}
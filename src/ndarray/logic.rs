@@ -0,0 +1,215 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Element-wise comparisons of Ndarrays, which result in boolean Ndarrays.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    /// Returns the function that compares two elements. Comparisons with NaN are `false`, except for `Ne`.
+    pub fn kernel<T: PartialOrd>(self) -> fn(T, T) -> bool {
+        match self {
+            Comparison::Eq => |a, b| a == b,
+            Comparison::Ne => |a, b| a != b,
+            Comparison::Lt => |a, b| a < b,
+            Comparison::Le => |a, b| a <= b,
+            Comparison::Gt => |a, b| a > b,
+            Comparison::Ge => |a, b| a >= b,
+        }
+    }
+}
+
+/// Element-wise logical operations of two Ndarrays.
+#[derive(Clone, Copy, PartialEq)]
+pub enum LogicalOp {
+    And,
+    Or,
+    Xor,
+}
+
+impl LogicalOp {
+    /// Returns the function that combines two booleans.
+    pub fn kernel(self) -> fn(bool, bool) -> bool {
+        match self {
+            LogicalOp::And => |a, b| a & b,
+            LogicalOp::Or => |a, b| a | b,
+            LogicalOp::Xor => |a, b| a ^ b,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Tests element-wise if the elements of the Ndarray are equal to `other`, which is a Ndarray or a number. The shapes of both operands are broadcast against each other.
+    pub fn eq(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.comparison(other, Comparison::Eq)
+    }
+
+    /// Tests element-wise if the elements of the Ndarray are not equal to `other`, which is a Ndarray or a number. The shapes of both operands are broadcast against each other.
+    pub fn ne(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.comparison(other, Comparison::Ne)
+    }
+
+    /// Tests element-wise if the elements of the Ndarray are less than `other`, which is a Ndarray or a number. The shapes of both operands are broadcast against each other.
+    pub fn lt(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.comparison(other, Comparison::Lt)
+    }
+
+    /// Tests element-wise if the elements of the Ndarray are less than or equal to `other`, which is a Ndarray or a number. The shapes of both operands are broadcast against each other.
+    pub fn le(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.comparison(other, Comparison::Le)
+    }
+
+    /// Tests element-wise if the elements of the Ndarray are greater than `other`, which is a Ndarray or a number. The shapes of both operands are broadcast against each other.
+    pub fn gt(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.comparison(other, Comparison::Gt)
+    }
+
+    /// Tests element-wise if the elements of the Ndarray are greater than or equal to `other`, which is a Ndarray or a number. The shapes of both operands are broadcast against each other.
+    pub fn ge(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.comparison(other, Comparison::Ge)
+    }
+
+    /// Computes the element-wise logical and of two Ndarrays. Elements that aren't zero count as `true`.
+    #[wasm_bindgen(js_name = logicalAnd)]
+    pub fn logical_and(&self, other: &Ndarray) -> Result<Ndarray, JsValue> {
+        self.logical_op(other, LogicalOp::And)
+    }
+
+    /// Computes the element-wise logical or of two Ndarrays. Elements that aren't zero count as `true`.
+    #[wasm_bindgen(js_name = logicalOr)]
+    pub fn logical_or(&self, other: &Ndarray) -> Result<Ndarray, JsValue> {
+        self.logical_op(other, LogicalOp::Or)
+    }
+
+    /// Computes the element-wise logical exclusive or of two Ndarrays. Elements that aren't zero count as `true`.
+    #[wasm_bindgen(js_name = logicalXor)]
+    pub fn logical_xor(&self, other: &Ndarray) -> Result<Ndarray, JsValue> {
+        self.logical_op(other, LogicalOp::Xor)
+    }
+
+    /// Negates the elements of the Ndarray. Elements that aren't zero count as `true`.
+    #[wasm_bindgen(js_name = logicalNot)]
+    pub fn logical_not(&self) -> Ndarray {
+        Ndarray(self.0.convert::<bool>().map(|x| !x).into_union())
+    }
+
+    /// Selects the elements for which the boolean Ndarray `mask` is `true` and returns them in row-major order as a new one-dimensional Ndarray.
+    ///
+    /// The mask must have the same shape as the Ndarray.
+    pub fn mask(&self, mask: &Ndarray) -> Result<Ndarray, JsValue> {
+        let mask = match &mask.0 {
+            NdarrayUnion::Bool(mask) => mask,
+            _ => return Err(JsValue::from_str("Mask must be a boolean Ndarray.")),
+        };
+        if &mask.shape != self.shape() {
+            return Err(JsValue::from_str(&format!(
+                "Mask of shape {:?} doesn't match Ndarray of shape {:?}.",
+                mask.shape,
+                self.shape()
+            )));
+        }
+        Ok(Ndarray(dispatch!(&self.0, ndarray => {
+            let data = ndarray
+                .data_indices()
+                .zip(mask.data_indices())
+                .filter(|&(_, j)| mask.data[j])
+                .map(|(i, _)| ndarray.data[i])
+                .collect::<Vec<_>>();
+            let len = data.len();
+            NdarrayBase::from_vec(data, vec![len]).into_union()
+        })))
+    }
+
+    /// Returns the elements of `a` where `cond` is `true` and the elements of `b` otherwise. Elements of `cond` that aren't zero count as `true`.
+    ///
+    /// The shapes of all three Ndarrays are broadcast against each other and `a` and `b` are converted to the promoted type of their element types.
+    #[wasm_bindgen(js_name = where)]
+    pub fn select(cond: &Ndarray, a: &Ndarray, b: &Ndarray) -> Result<Ndarray, JsValue> {
+        let dtype = DType::promote(a.0.dtype(), b.0.dtype());
+        let result = with_dtype!(dtype, T => select_base(
+            &cond.0.convert::<bool>(),
+            &a.0.convert::<T>(),
+            &b.0.convert::<T>(),
+        )
+        .map(NdarrayBase::into_union));
+        result.map(Ndarray).map_err(|err| JsValue::from_str(&err))
+    }
+}
+
+/// Picks the elements of `a` where `cond` is `true` and the elements of `b` otherwise, after broadcasting all three arrays to a common shape.
+pub fn select_base<T: Copy>(
+    cond: &NdarrayBase<bool>,
+    a: &NdarrayBase<T>,
+    b: &NdarrayBase<T>,
+) -> Result<NdarrayBase<T>, String> {
+    let shape = broadcast_shapes(&[&cond.shape, &a.shape, &b.shape])?;
+    let (cond, a, b) = (
+        cond.broadcast_to(&shape)?,
+        a.broadcast_to(&shape)?,
+        b.broadcast_to(&shape)?,
+    );
+    let data = cond
+        .data_indices()
+        .zip(a.data_indices().zip(b.data_indices()))
+        .map(|(k, (i, j))| if cond.data[k] { a.data[i] } else { b.data[j] })
+        .collect();
+    Ok(NdarrayBase::from_vec(data, shape))
+}
+
+impl Ndarray {
+    /// Compares the Ndarray element-wise with `other`, which is a Ndarray or a javascript number.
+    fn comparison(&self, other: &JsValue, comparison: Comparison) -> Result<Ndarray, JsValue> {
+        match other.as_f64() {
+            Some(number) => self.compare_scalar(number, comparison),
+            None => {
+                let other = Ndarray::borrow_js(other)
+                    .map_err(|_| JsValue::from_str("Expected a Ndarray or a number."))?;
+                self.compare(&other, comparison)
+            }
+        }
+    }
+
+    /// Compares every element of the Ndarray with a javascript number, which is weakly typed in the same way as in `scalar_op`.
+    pub fn compare_scalar(&self, number: f64, comparison: Comparison) -> Result<Ndarray, JsValue> {
+        let dtype = ops::scalar_dtype(self.0.dtype(), number);
+        let result = with_dtype!(dtype, T => {
+            let scalar = ops::scalar_value::<T>(number, dtype)?;
+            let kernel = comparison.kernel::<T>();
+            self.0.convert::<T>().map(|x| kernel(x, scalar)).into_union()
+        });
+        Ok(Ndarray(result))
+    }
+
+    /// Compares two Ndarrays element-wise after converting both to the promoted type of their element types.
+    pub fn compare(&self, other: &Ndarray, comparison: Comparison) -> Result<Ndarray, JsValue> {
+        let dtype = DType::promote(self.0.dtype(), other.0.dtype());
+        let result = with_dtype!(dtype, T => ops::zip_with(
+            &self.0.convert::<T>(),
+            &other.0.convert::<T>(),
+            comparison.kernel::<T>(),
+        ));
+        result
+            .map(|x| Ndarray(x.into_union()))
+            .map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Applies a logical operation element-wise to two Ndarrays after converting both to booleans.
+    pub fn logical_op(&self, other: &Ndarray, op: LogicalOp) -> Result<Ndarray, JsValue> {
+        ops::zip_with(
+            &self.0.convert::<bool>(),
+            &other.0.convert::<bool>(),
+            op.kernel(),
+        )
+        .map(|x| Ndarray(x.into_union()))
+        .map_err(|err| JsValue::from_str(&err))
+    }
+}
@@ -0,0 +1,138 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Normalizes the possibly negative `axes` of an array with `ndim` dimensions and checks that they form a permutation of its axes.
+fn parse_permutation(axes: &js_sys::Array, ndim: usize) -> Result<Vec<usize>, JsValue> {
+    let axes = js_interop::into_vec_isize(axes)?
+        .into_iter()
+        .map(|x| normalize_axis(x, ndim))
+        .collect::<Result<Vec<usize>, String>>()
+        .map_err(|err| JsValue::from_str(&err))?;
+    let mut sorted = axes.clone();
+    sorted.sort_unstable();
+    if sorted.into_iter().eq(0..ndim) {
+        Ok(axes)
+    } else {
+        Err(JsValue::from_str(
+            "Axes must be a permutation of the axes of the Ndarray.",
+        ))
+    }
+}
+
+impl Ndarray {
+    /// Creates a view with the axes reordered, so that axis `i` of the view is axis `axes[i]` of the Ndarray.
+    pub fn permuted(&self, axes: &[usize]) -> Ndarray {
+        Ndarray(dispatch!(&self.0, ndarray => ndarray.permute_axes(axes).into_union()))
+    }
+
+    /// Creates a view with the order of the elements reversed along all `axes`.
+    pub fn flipped(&self, axes: &[usize]) -> Ndarray {
+        Ndarray(dispatch!(&self.0, ndarray => {
+            let mut view = ndarray.clone();
+            for &axis in axes {
+                view = view.flip_axis(axis);
+            }
+            view.into_union()
+        }))
+    }
+
+    /// Turns the possibly negative `axis` into an axis of the Ndarray.
+    fn axis(&self, axis: isize) -> Result<usize, JsValue> {
+        normalize_axis(axis, self.shape().len()).map_err(|err| JsValue::from_str(&err))
+    }
+}
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Returns a view with the axes reordered according to `axes`, which is a permutation of the axes. Without `axes` the order of the axes is reversed.
+    ///
+    /// Axis `i` of the view is axis `axes[i]` of the Ndarray. Only the shape and strides are permuted, the data is not copied.
+    pub fn transpose(&self, axes: Option<js_sys::Array>) -> Result<Ndarray, JsValue> {
+        let ndim = self.shape().len();
+        let axes = match axes {
+            Some(axes) => parse_permutation(&axes, ndim)?,
+            None => (0..ndim).rev().collect(),
+        };
+        Ok(self.permuted(&axes))
+    }
+
+    /// Returns a view with the axes reordered according to `axes`, which is a permutation of the axes. Axis `i` of the view is axis `axes[i]` of the Ndarray.
+    #[wasm_bindgen(js_name = permuteAxes)]
+    pub fn permute_axes(&self, axes: &js_sys::Array) -> Result<Ndarray, JsValue> {
+        Ok(self.permuted(&parse_permutation(axes, self.shape().len())?))
+    }
+
+    /// Returns a view with the order of the axes reversed, the same as `transpose()`.
+    #[wasm_bindgen(getter = T)]
+    pub fn transposed(&self) -> Ndarray {
+        let axes = (0..self.shape().len()).rev().collect::<Vec<usize>>();
+        self.permuted(&axes)
+    }
+
+    /// Returns a view with the axes `a` and `b` interchanged.
+    #[wasm_bindgen(js_name = swapAxes)]
+    pub fn swap_axes(&self, a: isize, b: isize) -> Result<Ndarray, JsValue> {
+        let mut axes = (0..self.shape().len()).collect::<Vec<usize>>();
+        axes.swap(self.axis(a)?, self.axis(b)?);
+        Ok(self.permuted(&axes))
+    }
+
+    /// Returns a view with the axis `source` moved to the position `destination`. The other axes keep their order.
+    #[wasm_bindgen(js_name = moveAxis)]
+    pub fn move_axis(&self, source: isize, destination: isize) -> Result<Ndarray, JsValue> {
+        let source = self.axis(source)?;
+        let destination = self.axis(destination)?;
+        let mut axes = (0..self.shape().len())
+            .filter(|&i| i != source)
+            .collect::<Vec<usize>>();
+        axes.insert(destination, source);
+        Ok(self.permuted(&axes))
+    }
+
+    /// Returns a view with the order of the elements reversed along `axis`, which is a number, an Array of numbers or, by default, all axes.
+    ///
+    /// The view walks through the memory of the Ndarray backwards with a negative stride, the data is not copied.
+    pub fn flip(&self, axis: JsValue) -> Result<Ndarray, JsValue> {
+        Ok(self.flipped(&reduce::parse_axes(&axis, self.shape().len())?))
+    }
+
+    /// Returns a view with the order of the rows, the first axis, reversed.
+    pub fn flipud(&self) -> Result<Ndarray, JsValue> {
+        Ok(self.flipped(&[self.axis(0)?]))
+    }
+
+    /// Returns a view with the order of the columns, the second axis, reversed.
+    pub fn fliplr(&self) -> Result<Ndarray, JsValue> {
+        if self.shape().len() < 2 {
+            return Err(JsValue::from_str(
+                "Fliplr requires a Ndarray with at least two dimensions.",
+            ));
+        }
+        Ok(self.flipped(&[1]))
+    }
+
+    /// Returns a view that is rotated by 90 degrees `k` times (default 1) in the plane of the two `axes` (default `[0, 1]`). The rotation goes from the first towards the second axis, which is counterclockwise for images.
+    ///
+    /// The rotation combines flipping and swapping the axes, so the data is not copied.
+    pub fn rot90(&self, k: Option<isize>, axes: Option<js_sys::Array>) -> Result<Ndarray, JsValue> {
+        let (a, b) = match axes {
+            Some(axes) => match &js_interop::into_vec_isize(&axes)?[..] {
+                &[a, b] => (self.axis(a)?, self.axis(b)?),
+                _ => return Err(JsValue::from_str("Rot90 requires exactly two axes.")),
+            },
+            None => (self.axis(0)?, self.axis(1)?),
+        };
+        if a == b {
+            return Err(JsValue::from_str("Rot90 requires two different axes."));
+        }
+        let mut swapped = (0..self.shape().len()).collect::<Vec<usize>>();
+        swapped.swap(a, b);
+        Ok(match k.unwrap_or(1).rem_euclid(4) {
+            0 => self.view(),
+            1 => self.flipped(&[b]).permuted(&swapped),
+            2 => self.flipped(&[a, b]),
+            _ => self.permuted(&swapped).flipped(&[b]),
+        })
+    }
+}
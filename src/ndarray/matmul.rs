@@ -0,0 +1,214 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Side length of the square blocks in which matrix products are computed. Three blocks of `f64` fit into a 32KB L1 cache.
+const BLOCK: usize = 32;
+
+/// Products of two Ndarrays that sum over pairs of axes.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Product {
+    Matmul,
+    Dot,
+    Vdot,
+    Inner,
+    Outer,
+}
+
+/// Multiplies the row-major matrices `a` (m x k) and `b` (k x n) and adds the result to `c` (m x n).
+///
+/// The loops are blocked so that the parts of `a`, `b` and `c` that are used together stay in the cache, and the innermost loop runs over contigious rows of `b` and `c`.
+pub fn gemm<T: Arithmetic>(a: &[T], b: &[T], c: &mut [T], m: usize, k: usize, n: usize) {
+    for i0 in (0..m).step_by(BLOCK) {
+        for p0 in (0..k).step_by(BLOCK) {
+            for j0 in (0..n).step_by(BLOCK) {
+                let j1 = (j0 + BLOCK).min(n);
+                for i in i0..(i0 + BLOCK).min(m) {
+                    let row = &mut c[i * n + j0..i * n + j1];
+                    for p in p0..(p0 + BLOCK).min(k) {
+                        let x = a[i * k + p];
+                        for (z, &y) in row.iter_mut().zip(b[p * n + j0..p * n + j1].iter()) {
+                            *z = z.add(x.mul(y));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Sums the products of the last axis of `a` and axis `axis` of `b`.
+///
+/// The result has the remaining axes of `a` followed by the remaining axes of `b`. Both operands are packed into contigious buffers first, so the blocked kernel works for arbitrary strides and formats.
+fn contract<T: Arithmetic>(
+    a: &NdarrayBase<T>,
+    b: &NdarrayBase<T>,
+    axis: usize,
+) -> Result<NdarrayBase<T>, String> {
+    let k = a.shape[a.shape.len() - 1];
+    if b.shape[axis] != k {
+        return Err(format!(
+            "Shapes {:?} and {:?} are not aligned: {} (last axis of first operand) != {} (axis {} of second operand).",
+            a.shape, b.shape, k, b.shape[axis], axis
+        ));
+    }
+    let order = std::iter::once(axis)
+        .chain((0..b.shape.len()).filter(|&i| i != axis))
+        .collect::<Vec<usize>>();
+    let b_data = b.permute_axes(&order).to_vec();
+    let a_data = a.to_vec();
+    let m = a.shape[..a.shape.len() - 1].iter().product::<usize>();
+    let n = order[1..].iter().map(|&i| b.shape[i]).product::<usize>();
+    let mut data = vec![T::ZERO; m * n];
+    gemm(&a_data, &b_data, &mut data, m, k, n);
+    let shape = a.shape[..a.shape.len() - 1]
+        .iter()
+        .chain(order[1..].iter().map(|&i| &b.shape[i]))
+        .cloned()
+        .collect();
+    Ok(NdarrayBase::from_vec(data, shape))
+}
+
+/// Matrix product of the last two axes of `a` and `b`, with the leading axes broadcast as a batch of matrices.
+///
+/// One-dimensional operands are treated as a row (`a`) or column (`b`) vector, and the corresponding axis is removed from the result.
+fn matmul_base<T: Arithmetic>(
+    a: &NdarrayBase<T>,
+    b: &NdarrayBase<T>,
+) -> Result<NdarrayBase<T>, String> {
+    if a.shape.is_empty() || b.shape.is_empty() {
+        return Err(String::from(
+            "Matmul is not defined for zero-dimensional arrays.",
+        ));
+    }
+    let a_vector = a.shape.len() == 1;
+    let b_vector = b.shape.len() == 1;
+    let (a_matrix, b_matrix);
+    let a = if a_vector {
+        a_matrix = NdarrayBase::from_vec(a.to_vec(), vec![1, a.shape[0]]);
+        &a_matrix
+    } else {
+        a
+    };
+    let b = if b_vector {
+        b_matrix = NdarrayBase::from_vec(b.to_vec(), vec![b.shape[0], 1]);
+        &b_matrix
+    } else {
+        b
+    };
+    let (m, k) = (a.shape[a.shape.len() - 2], a.shape[a.shape.len() - 1]);
+    let (l, n) = (b.shape[b.shape.len() - 2], b.shape[b.shape.len() - 1]);
+    if k != l {
+        return Err(format!(
+            "Matmul: core dimensions of shapes {:?} and {:?} don't match ({} != {}).",
+            a.shape, b.shape, k, l
+        ));
+    }
+    let batch = broadcast::broadcast_shapes(&[
+        &a.shape[..a.shape.len() - 2],
+        &b.shape[..b.shape.len() - 2],
+    ])?;
+    let a_data = a.broadcast_to(&[&batch[..], &[m, k]].concat())?.to_vec();
+    let b_data = b.broadcast_to(&[&batch[..], &[k, n]].concat())?.to_vec();
+    let mut data = vec![T::ZERO; batch.iter().product::<usize>() * m * n];
+    for (i, c) in data.chunks_mut((m * n).max(1)).enumerate() {
+        gemm(
+            &a_data[i * m * k..(i + 1) * m * k],
+            &b_data[i * k * n..(i + 1) * k * n],
+            c,
+            m,
+            k,
+            n,
+        );
+    }
+    let mut shape = batch;
+    if !a_vector {
+        shape.push(m);
+    }
+    if !b_vector {
+        shape.push(n);
+    }
+    Ok(NdarrayBase::from_vec(data, shape))
+}
+
+/// Computes one of the products for two NdarrayBases with the same element type.
+fn product_base<T: Arithmetic>(
+    a: &NdarrayBase<T>,
+    b: &NdarrayBase<T>,
+    product: Product,
+) -> Result<NdarrayBase<T>, String> {
+    match product {
+        Product::Matmul => matmul_base(a, b),
+        // products with a zero-dimensional operand are element-wise multiplications
+        Product::Dot | Product::Inner if a.shape.is_empty() || b.shape.is_empty() => {
+            ops::zip_with(a, b, T::mul)
+        }
+        Product::Dot => contract(a, b, b.shape.len().max(2) - 2),
+        Product::Inner => contract(a, b, b.shape.len() - 1),
+        Product::Vdot => {
+            if a.size() != b.size() {
+                return Err(String::from(
+                    "Vdot requires both operands to have the same number of elements.",
+                ));
+            }
+            contract(
+                &NdarrayBase::from_vec(a.to_vec(), vec![a.size()]),
+                &NdarrayBase::from_vec(b.to_vec(), vec![b.size()]),
+                0,
+            )
+        }
+        Product::Outer => contract(
+            &NdarrayBase::from_vec(a.to_vec(), vec![a.size(), 1]),
+            &NdarrayBase::from_vec(b.to_vec(), vec![1, b.size()]),
+            0,
+        ),
+    }
+}
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Matrix product of two Ndarrays.
+    ///
+    /// Arrays with more than two dimensions are treated as stacks of matrices in the last two axes, which are broadcast against each other. One-dimensional arrays are treated as vectors. Returns a number for the product of two vectors, otherwise a Ndarray.
+    pub fn matmul(&self, other: &Ndarray) -> Result<JsValue, JsValue> {
+        self.product(other, Product::Matmul)
+    }
+
+    /// Dot product of two Ndarrays.
+    ///
+    /// Sums over the last axis of this Ndarray and the second-to-last axis of `other` (or its only axis). For one- and two-dimensional arrays this is the same as `matmul`.
+    pub fn dot(&self, other: &Ndarray) -> Result<JsValue, JsValue> {
+        self.product(other, Product::Dot)
+    }
+
+    /// Dot product of the flattened Ndarrays, which uses the complex conjugates of the elements of this Ndarray. Returns a number.
+    pub fn vdot(&self, other: &Ndarray) -> Result<JsValue, JsValue> {
+        self.conj().product(other, Product::Vdot)
+    }
+
+    /// Inner product of two Ndarrays, which sums over the last axes of both.
+    pub fn inner(&self, other: &Ndarray) -> Result<JsValue, JsValue> {
+        self.product(other, Product::Inner)
+    }
+
+    /// Outer product of the flattened Ndarrays. Returns a two-dimensional Ndarray.
+    pub fn outer(&self, other: &Ndarray) -> Result<JsValue, JsValue> {
+        self.product(other, Product::Outer)
+    }
+}
+
+impl Ndarray {
+    /// Computes a product of two Ndarrays. Both operands are converted to the promoted type of their element types.
+    fn product(&self, other: &Ndarray, product: Product) -> Result<JsValue, JsValue> {
+        let dtype = DType::promote(self.0.dtype(), other.0.dtype());
+        let result = with_dtype!(dtype, T => product_base(
+            &self.0.convert::<T>(),
+            &other.0.convert::<T>(),
+            product,
+        )
+        .map(NdarrayBase::into_union));
+        result
+            .map(|result| Ndarray(result).into_js_value())
+            .map_err(|err| JsValue::from_str(&err))
+    }
+}
@@ -2,12 +2,35 @@ use std::convert::TryInto;
 use std::rc::Rc;
 
 use js_sys;
+use wasm_bindgen::convert::TryFromJsValue;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 
 use crate::js_interop;
+use crate::utils;
 
+#[macro_use]
+mod dtype;
+mod broadcast;
+mod complex;
+mod creation;
+mod half;
+mod index;
+mod logic;
+mod manipulation;
+mod matmul;
+mod ops;
+mod reduce;
 pub mod test;
 
+pub use broadcast::broadcast_shapes;
+pub use complex::Complex;
+pub use creation::parse_dtype;
+pub use dtype::{DType, Element};
+pub use half::{BFloat16, Float16};
+pub use logic::{Comparison, LogicalOp};
+pub use ops::{Arithmetic, BinaryOp};
+
 /// N-dimensional arary for numerical computations in javascript.
 ///
 /// Ndarray is a n-dimensional container for homogeneous data. It enables efficient manipulation
@@ -17,7 +40,7 @@ pub mod test;
 ///
 ///
 #[wasm_bindgen]
-pub struct Ndarray(NdarrayUnion);
+pub struct Ndarray(pub(crate) NdarrayUnion);
 
 /// Union of multiple `NdarrayBase<T>` types with different type parameters `T`.
 ///
@@ -26,8 +49,21 @@ pub struct Ndarray(NdarrayUnion);
 /// # Example
 ///
 pub enum NdarrayUnion {
+    Bool(NdarrayBase<bool>),
+    I8(NdarrayBase<i8>),
+    U8(NdarrayBase<u8>),
+    I16(NdarrayBase<i16>),
+    U16(NdarrayBase<u16>),
     I32(NdarrayBase<i32>),
+    U32(NdarrayBase<u32>),
+    I64(NdarrayBase<i64>),
+    U64(NdarrayBase<u64>),
+    F16(NdarrayBase<Float16>),
+    BF16(NdarrayBase<BFloat16>),
+    F32(NdarrayBase<f32>),
     F64(NdarrayBase<f64>),
+    C64(NdarrayBase<Complex<f32>>),
+    C128(NdarrayBase<Complex<f64>>),
 }
 
 /// The actual implementation of the strided n-dimensional array.
@@ -36,107 +72,108 @@ pub enum NdarrayUnion {
 ///
 /// # Example
 ///
+#[derive(Clone)]
 pub struct NdarrayBase<T> {
     pub data: Rc<[T]>,
     pub shape: Vec<usize>,
-    pub strides: Vec<usize>,
+    pub strides: Vec<isize>,
+    pub offset: usize,
     pub format: Format,
 }
 
 /// Describes if a `Ndarray` behaves like a contigious subview (`Slice`), a set along selected axis (`Pick`) or normally (`None`)
 ///
 /// Subview is used because traits cannot be used at the wasm boundary. To still represent Ndarray as a polymorphic type, its behavior changes according to the value of its Subview field.
+#[derive(Clone)]
 pub enum Format {
     Slice(Vec<usize>),
     Slices(Vec<Vec<usize>>),
     None,
 }
 
-/// Struct used for iterators that contains shared references to an ArrayBase object
-pub struct NdarrayView<'a, T> {
-    pub data: &'a [T],
-    pub shape: &'a [usize],
-    pub strides: &'a [usize],
-    pub format: FormatView<'a>,
-    pub len: usize,
-}
-
-pub enum FormatView<'a> {
-    Slice(&'a [usize]),
-    Slices(&'a [Vec<usize>]),
-    None,
-}
-
 #[wasm_bindgen]
 pub struct NdarrayMut(NdarrayUnionMut);
 
 pub enum NdarrayUnionMut {
+    Bool(NdarrayBaseMut<bool>),
+    I8(NdarrayBaseMut<i8>),
+    U8(NdarrayBaseMut<u8>),
+    I16(NdarrayBaseMut<i16>),
+    U16(NdarrayBaseMut<u16>),
     I32(NdarrayBaseMut<i32>),
+    U32(NdarrayBaseMut<u32>),
+    I64(NdarrayBaseMut<i64>),
+    U64(NdarrayBaseMut<u64>),
+    F16(NdarrayBaseMut<Float16>),
+    BF16(NdarrayBaseMut<BFloat16>),
+    F32(NdarrayBaseMut<f32>),
     F64(NdarrayBaseMut<f64>),
+    C64(NdarrayBaseMut<Complex<f32>>),
+    C128(NdarrayBaseMut<Complex<f64>>),
 }
 
 pub struct NdarrayBaseMut<T> {
     pub data: *mut [T],
     pub shape: Vec<usize>,
-    pub strides: Vec<usize>,
+    pub strides: Vec<isize>,
+    pub offset: usize,
     pub format: Format,
 }
 
-/// Struct used for iterators that contains mutable references to an ArrayBase object
-pub struct NdarrayViewMut<'a, T> {
-    pub data: &'a mut [T],
-    pub shape: &'a [usize],
-    pub strides: &'a [usize],
-    pub format: FormatView<'a>,
-    pub len: usize,
-}
-
 #[wasm_bindgen]
 impl Ndarray {
     /// Constructor for the Ndarray struct.
     ///
     /// Can be constucted with:
-    /// - a nested Javascript Array
-    /// - a linear Int32Array
-    /// - a linear Float64Array
+    /// - a number, which results in a zero-dimensional Ndarray
+    /// - a nested Javascript Array of numbers, or of booleans which results in a boolean Ndarray
+    /// - a linear typed array (Int8Array, Uint8Array, Uint8ClampedArray, Int16Array, Uint16Array, Int32Array, Uint32Array, BigInt64Array, BigUint64Array, Float32Array or Float64Array), which keeps its element type
     ///
     /// Example:
     ///
     #[wasm_bindgen(constructor)]
     pub fn new(input: JsValue) -> Ndarray {
+        utils::set_panic_hook();
         match js_interop::unwrap_js_value(input) {
             Ok(data) => match data {
+                js_interop::JsType::Number(number) => Ndarray(NdarrayUnion::F64(
+                    NdarrayBase::from_vec(vec![number], vec![]),
+                )),
                 js_interop::JsType::Array(array) => {
                     let mut shape: Vec<usize> = Vec::new();
                     shape.push(array.length().try_into().unwrap());
                     let flat_array = js_interop::flatten_jsarray(array, &mut shape);
+                    if flat_array.get(0).as_bool().is_some() {
+                        let data = flat_array.iter().map(|x| x.as_bool().unwrap()).collect();
+                        return Ndarray(NdarrayBase::from_vec(data, shape).into_union());
+                    }
                     let data: Rc<[f64]> = flat_array.iter().map(|x| x.as_f64().unwrap()).collect();
                     Ndarray(NdarrayUnion::F64(NdarrayBase {
-                        data: data,
+                        data,
                         strides: Ndarray::get_strides_from_shape(&shape),
-                        shape: shape,
+                        shape,
+                        offset: 0,
                         format: Format::None,
                     }))
                 }
-                js_interop::JsType::Int32Array(array) => {
-                    let vec = array.to_vec();
-                    Ndarray(NdarrayUnion::I32(NdarrayBase {
-                        shape: vec![vec.len()],
-                        data: Rc::from(vec),
-                        strides: vec![1],
-                        format: Format::None,
-                    }))
+                js_interop::JsType::Int8Array(array) => Ndarray::from_typed_array(array.to_vec()),
+                js_interop::JsType::Uint8Array(array) => Ndarray::from_typed_array(array.to_vec()),
+                js_interop::JsType::Int16Array(array) => Ndarray::from_typed_array(array.to_vec()),
+                js_interop::JsType::Uint16Array(array) => Ndarray::from_typed_array(array.to_vec()),
+                js_interop::JsType::Int32Array(array) => Ndarray::from_typed_array(array.to_vec()),
+                js_interop::JsType::Uint32Array(array) => Ndarray::from_typed_array(array.to_vec()),
+                js_interop::JsType::BigInt64Array(array) => {
+                    Ndarray::from_typed_array(array.to_vec())
+                }
+                js_interop::JsType::BigUint64Array(array) => {
+                    Ndarray::from_typed_array(array.to_vec())
+                }
+                js_interop::JsType::Float32Array(array) => {
+                    Ndarray::from_typed_array(array.to_vec())
                 }
                 js_interop::JsType::Float64Array(array) => {
-                    let vec = array.to_vec();
-                    Ndarray(NdarrayUnion::F64(NdarrayBase {
-                        shape: vec![vec.len()],
-                        data: Rc::from(vec),
-                        strides: vec![1],
-                        format: Format::None,
-                    }))
+                    Ndarray::from_typed_array(array.to_vec())
                 }
-                _ => panic!("Input must be some kind of Array."),
             },
             Err(_) => panic!("Datatype not supported"),
         }
@@ -144,12 +181,11 @@ impl Ndarray {
 
     /// Changes the shape of the given Ndarray.
     ///
-    /// Length of the provided Array must be equal to the number of dimensions.
+    /// The product of the provided shape must be equal to the number of elements. The elements keep their row-major order. A view whose elements aren't stored contiguously in row-major order, for example a transposed or stepped slice, is copied first and doesn't reference the original memory afterwards.
     pub fn reshape(&mut self, shape: &js_sys::Array) -> Result<(), JsValue> {
-        let vec = js_interop::into_vec_usize(shape)?;
+        let vec = js_interop::into_shape(shape)?;
         if vec.iter().product::<usize>() == self.shape().iter().product::<usize>() {
-            self.set_strides(Ndarray::get_strides_from_shape(&vec));
-            self.set_shape(vec);
+            self.0 = dispatch!(&self.0, ndarray => ndarray.reshaped(vec).into_union());
             Ok(())
         } else {
             Err(JsValue::from_str("Shape doesn't fit data."))
@@ -159,7 +195,7 @@ impl Ndarray {
     /// Returns a single entry with the indices given through a Javascript Array
     pub fn get(&self, input: js_sys::Array) -> Result<JsValue, JsValue> {
         // TODO: introduce bound checks
-        assert_eq!(js_sys::Array::is_array(&input.get(0)), false);
+        assert!(!js_sys::Array::is_array(&input.get(0)));
         let indices = js_interop::into_vec_isize(&input)?;
         let shape: &Vec<usize> = self.shape();
         let indices: Vec<usize> = indices
@@ -168,226 +204,593 @@ impl Ndarray {
             .map(|(i, x)| ((x + shape[i] as isize) % (shape[i] as isize)) as usize)
             .collect::<Vec<usize>>();
         assert_eq!(self.strides().len(), indices.len());
-        let index = match self.format() {
-            Format::None => self
-                .strides()
-                .iter()
-                .zip(indices.iter())
-                .map(|(x, y)| x * y)
-                .sum::<usize>(),
-
-            Format::Slice(offset) => self
-                .strides()
-                .iter()
-                .zip(indices.iter())
-                .enumerate()
-                .map(|(i, (x, y))| *x * (offset[i] + y))
-                .sum::<usize>(),
-            Format::Slices(slices) => self
-                .strides()
-                .iter()
-                .zip(slices.iter())
-                .enumerate()
-                .map(|(i, (x, y))| x * y[indices[i]])
-                .sum::<usize>(),
-        };
-        match &self.0 {
-            NdarrayUnion::I32(ndarray) => Ok(JsValue::from_f64(ndarray.data[index] as f64)),
-            NdarrayUnion::F64(ndarray) => Ok(JsValue::from_f64(ndarray.data[index])),
-        }
+        dispatch!(&self.0, ndarray => Ok(ndarray.data[ndarray.data_index(&indices)].to_js()))
     }
 
     /// Creates a slice of the corresponding Ndarray. Returns a new Ndarray which still references the same memory as the original, but has potentially a different offset, shape and strides.
     ///
+    /// Every leading axis is sliced with an Array `[start, stop, step]` with Python semantics: `stop` is exclusive, negative bounds are counted from the end of the axis and `step` defaults to one. Negative steps walk backwards from `start`. Missing, `null` or `undefined` entries leave the range open, so `[null, null, -1]` reverses an axis, and a `null` definition keeps the whole axis. Bounds outside of the axis are errors.
+    ///
     /// Because the slice references the original memory, it should be used for computations but it shouldn't be assigned to a new variable. Assigning to a new variable increases the reference count and the underlying data can not be mutated afterwards. Weblab-ndarray doesn't allow mutating data if the reference count of a value is higher than one.
     ///
     /// # Example
     ///
     pub fn slice(&self, input: js_sys::Array) -> Result<Ndarray, JsValue> {
         let input = input.to_vec();
-        let strides = self.strides();
-        // iterator of tuple can be split into a pair of two collections
-        let (offset, rest) = input
+        let shape = self.shape();
+        if input.len() > shape.len() {
+            return Err(JsValue::from_str(
+                "More slice definitions than dimensions of the Ndarray.",
+            ));
+        }
+        let ranges = input
             // iterate over all slice defintions
             .iter()
-            .enumerate()
-            .map(|(i, x)| {
-                // create vector of isizes from Array of JsValues
-                match js_interop::into_vec_isize(&js_sys::Array::from(x)) {
-                    // match the length of each slice definition
-                    Ok(array) => match array.len() {
-                        // if it has two entries, the offset and the shape changes, but not the strides
-                        2 => Ok((
-                            array[0] as usize,
-                            ((array[1] - array[0] + 1) as usize, strides[i]),
-                        )),
-                        // if it has 3 entries, offset, shape and strides change
-                        3 => Ok((
-                            array[0] as usize,
-                            (
-                                (array[1] - array[0] + 1) as usize,
-                                (array[2] as usize) * strides[i],
-                            ),
-                        )),
-                        // Slice is wrongly defined
-                        _ => Err(JsValue::from_str(
-                            "Not the right number of entries in slice definition.",
-                        )),
-                    },
-                    Err(err) => Err(err),
+            .zip(shape.iter())
+            .map(|(x, &len)| {
+                // a missing definition selects the whole axis
+                if x.is_null() || x.is_undefined() {
+                    return Ok((0, len, 1));
                 }
+                if !js_sys::Array::is_array(x) {
+                    return Err(JsValue::from_str(
+                        "Slice definitions must be Arrays, null or undefined.",
+                    ));
+                }
+                // create vector of optional isizes from Array of JsValues
+                let array = js_interop::into_vec_option_isize(&js_sys::Array::from(x))?;
+                if array.len() > 3 {
+                    // Slice is wrongly defined
+                    return Err(JsValue::from_str(
+                        "Not the right number of entries in slice definition.",
+                    ));
+                }
+                let entry = |i: usize| array.get(i).copied().flatten();
+                normalize_range(entry(0), entry(1), entry(2), len)
+                    .map_err(|err| JsValue::from_str(&err))
             })
-            // collect first into result to see of any of the slices was wrongly defined, ? Returns Err if it did, otherwise continue with vector
-            .collect::<Result<Vec<(usize, (usize, usize))>, JsValue>>()?
-            .into_iter()
-            .unzip::<usize, (usize, usize), Vec<usize>, Vec<(usize, usize)>>();
-        // unzip the second pair
-        let (shape, strides): (Vec<usize>, Vec<usize>) = rest
-            .into_iter()
-            .unzip::<usize, usize, Vec<usize>, Vec<usize>>();
-        match &self.0 {
-            NdarrayUnion::I32(ndarray) => Ok(Ndarray(NdarrayUnion::I32(NdarrayBase {
-                data: ndarray.data.clone(),
-                strides: strides,
-                shape: shape,
-                format: Format::Slice(offset),
-            }))),
-            NdarrayUnion::F64(ndarray) => Ok(Ndarray(NdarrayUnion::F64(NdarrayBase {
-                data: ndarray.data.clone(),
-                strides: strides,
-                shape: shape,
-                format: Format::Slice(offset),
-            }))),
-        }
+            // collect into result to see if any of the slices was wrongly defined, ? Returns Err if it did, otherwise continue with vector
+            .collect::<Result<Vec<(usize, usize, isize)>, JsValue>>()?;
+        Ok(Ndarray(dispatch!(&self.0, ndarray => {
+            // contigious arrays become slices with an offset for every axis
+            let mut ndarray = ndarray.clone();
+            if let Format::None = ndarray.format {
+                ndarray.format = Format::Slice(vec![0; ndarray.shape.len()]);
+            }
+            ndarray.slice_axes(&ranges).into_union()
+        })))
+    }
+
+    /// Returns a new Ndarray that references the same memory as the original and has the same shape and strides.
+    pub fn view(&self) -> Ndarray {
+        Ndarray(dispatch!(&self.0, ndarray => ndarray.clone().into_union()))
     }
 
+    /// Creates a view that contains the elements at the given indices along the leading axes. Every index list is a Javascript Array of indices into the corresponding axis, axes without a list are kept completely.
+    ///
+    /// The indices refer to the Ndarray as it is, so slicing a view selects from the elements of the view. The returned Ndarray references the same memory as the original.
     pub fn slices(&self, input: js_sys::Array) -> Result<Ndarray, JsValue> {
         let input = input.to_vec();
         let input = input
             .iter()
             .map(|x| -> Result<Vec<usize>, JsValue> {
-                js_interop::into_vec_usize(&js_sys::Array::from(&x))
+                js_interop::into_vec_usize(&js_sys::Array::from(x))
             })
             .collect::<Result<Vec<Vec<usize>>, JsValue>>()?;
-        let shape = input.iter().map(|x| x.len()).collect::<Vec<usize>>();
-        match &self.0 {
-            NdarrayUnion::I32(ndarray) => Ok(Ndarray(NdarrayUnion::I32(NdarrayBase {
-                data: ndarray.data.clone(),
-                strides: self.strides().clone(),
-                shape: shape,
-                format: Format::Slices(input),
-            }))),
-            NdarrayUnion::F64(ndarray) => Ok(Ndarray(NdarrayUnion::F64(NdarrayBase {
-                data: ndarray.data.clone(),
-                strides: self.strides().clone(),
-                shape: shape,
-                format: Format::Slices(input),
-            }))),
+        let shape = self.shape();
+        if input.len() > shape.len() {
+            return Err(JsValue::from_str(
+                "More index lists than dimensions of the Ndarray.",
+            ));
+        }
+        for (axis, (indices, &len)) in input.iter().zip(shape.iter()).enumerate() {
+            if let Some(index) = indices.iter().find(|&&index| index >= len) {
+                return Err(JsValue::from_str(&format!(
+                    "Index {} is out of bounds for axis {} with length {}.",
+                    index, axis, len
+                )));
+            }
         }
+        Ok(Ndarray(
+            dispatch!(&self.0, ndarray => ndarray.select_axes(&input).into_union()),
+        ))
     }
 }
 
-impl Ndarray {
-    fn new_rust(input: Vec<f64>, shape: Vec<usize>) -> Result<Ndarray, String> {
-        Ok(Ndarray(NdarrayUnion::F64(NdarrayBase {
+impl<T> NdarrayBase<T> {
+    /// Creates a NdarrayBase from data that is stored contigiously in row-major order.
+    pub fn from_vec(data: Vec<T>, shape: Vec<usize>) -> NdarrayBase<T> {
+        NdarrayBase {
             strides: Ndarray::get_strides_from_shape(&shape),
-            shape: shape,
-            data: Rc::from(input),
+            shape,
+            data: Rc::from(data),
+            offset: 0,
             format: Format::None,
-        })))
+        }
     }
 
-    fn view_rust<'a>(&'a self) -> Result<NdarrayView<'a, f64>, String> {
-        match &self.0 {
-            NdarrayUnion::F64(ndarray) => Ok(NdarrayView {
-                data: &ndarray.data,
-                len: ndarray.shape.iter().product::<usize>(),
-                shape: &ndarray.shape,
-                strides: &ndarray.strides,
-                format: FormatView::None,
-            }),
-            NdarrayUnion::I32(_) => Err(String::from("Not the right Data")),
+    /// Creates a view with the axes reordered according to `axes`, which has to be a permutation of the axes of the array.
+    ///
+    /// Axis `i` of the view is axis `axes[i]` of the array. The data is not copied.
+    pub fn permute_axes(&self, axes: &[usize]) -> NdarrayBase<T> {
+        NdarrayBase {
+            data: self.data.clone(),
+            shape: axes.iter().map(|&i| self.shape[i]).collect(),
+            strides: axes.iter().map(|&i| self.strides[i]).collect(),
+            offset: self.offset,
+            format: match &self.format {
+                Format::None => Format::None,
+                Format::Slice(offset) => Format::Slice(axes.iter().map(|&i| offset[i]).collect()),
+                Format::Slices(slices) => {
+                    Format::Slices(axes.iter().map(|&i| slices[i].clone()).collect())
+                }
+            },
+        }
+    }
+
+    /// Creates a view with the same shape that references the same memory.
+    pub fn view(&self) -> NdarrayBase<T> {
+        NdarrayBase {
+            data: self.data.clone(),
+            shape: self.shape.clone(),
+            strides: self.strides.clone(),
+            offset: self.offset,
+            format: self.format.clone(),
+        }
+    }
+
+    /// Creates a view that contains the elements `start + step * j` for `j < len` along the leading axes, given as `(start, len, step)` for each axis. Axes without a range are kept completely.
+    ///
+    /// Index lists of `Format::Slices` are narrowed down directly. Otherwise unit steps move the offset of `Format::Slice`, while other steps move the first selected element into the base offset and multiply the stride, which becomes negative for negative steps. The data is not copied.
+    pub fn slice_axes(&self, ranges: &[(usize, usize, isize)]) -> NdarrayBase<T> {
+        let mut view = self.view();
+        for (axis, &(start, len, step)) in ranges.iter().enumerate() {
+            view.shape[axis] = len;
+            let index = |j: usize| (start as isize + step * j as isize) as usize;
+            match &mut view.format {
+                Format::Slices(slices) => {
+                    slices[axis] = (0..len).map(|j| slices[axis][index(j)]).collect();
+                }
+                _ if len == 0 => (),
+                Format::Slice(offset) if step == 1 => offset[axis] += start,
+                format => {
+                    let first = match format {
+                        Format::Slice(offset) => std::mem::replace(&mut offset[axis], 0) + start,
+                        _ => start,
+                    };
+                    view.offset =
+                        (view.offset as isize + view.strides[axis] * first as isize) as usize;
+                    view.strides[axis] *= step;
+                }
+            }
         }
+        view
     }
+
+    /// Creates a view that contains the elements at the given `indices` along the leading axes. Axes without an index list are kept completely.
+    ///
+    /// The view has `Format::Slices` with the index lists of the current format translated into positions along the axes of the memory, so selecting from a slice or from another selection composes. The indices have to lie within `shape`.
+    pub fn select_axes(&self, indices: &[Vec<usize>]) -> NdarrayBase<T> {
+        let slices = (0..self.shape.len())
+            .map(|axis| {
+                let position = |index: usize| match &self.format {
+                    Format::None => index,
+                    Format::Slice(offset) => offset[axis] + index,
+                    Format::Slices(slices) => slices[axis][index],
+                };
+                match indices.get(axis) {
+                    Some(indices) => indices.iter().map(|&index| position(index)).collect(),
+                    None => (0..self.shape[axis]).map(position).collect(),
+                }
+            })
+            .collect::<Vec<Vec<usize>>>();
+        NdarrayBase {
+            data: self.data.clone(),
+            shape: slices.iter().map(|x| x.len()).collect(),
+            strides: self.strides.clone(),
+            offset: self.offset,
+            format: Format::Slices(slices),
+        }
+    }
+
+    /// Returns the position in `data` of the first element if the elements are stored contiguously in row-major order, which allows reshaping the array without copying.
+    pub fn contiguous_offset(&self) -> Option<usize> {
+        if self.size() == 0 {
+            return None;
+        }
+        if let Format::Slices(slices) = &self.format {
+            // consecutive indices behave like an offset
+            if slices
+                .iter()
+                .any(|x| x.windows(2).any(|x| x[1] != x[0] + 1))
+            {
+                return None;
+            }
+        }
+        let contiguous = Ndarray::get_strides_from_shape(&self.shape);
+        self.shape
+            .iter()
+            .zip(self.strides.iter().zip(contiguous.iter()))
+            .all(|(&len, (stride, expected))| len == 1 || stride == expected)
+            .then(|| self.data_index(&vec![0; self.shape.len()]))
+    }
+
+    /// Creates a view without `axis` that contains the elements at index zero along it. The axis must not be empty.
+    pub fn remove_axis(&self, axis: usize) -> NdarrayBase<T> {
+        let mut view = self.view();
+        view.shape.remove(axis);
+        let stride = view.strides.remove(axis);
+        let first = match &mut view.format {
+            Format::None => 0,
+            Format::Slice(offset) => offset.remove(axis),
+            Format::Slices(slices) => slices.remove(axis)[0],
+        };
+        view.offset = (view.offset as isize + stride * first as isize) as usize;
+        view
+    }
+
+    /// Creates a view with a new axis of length one at position `axis`.
+    pub fn insert_axis(&self, axis: usize) -> NdarrayBase<T> {
+        let mut view = self.view();
+        view.shape.insert(axis, 1);
+        view.strides.insert(axis, 0);
+        match &mut view.format {
+            Format::None => (),
+            Format::Slice(offset) => offset.insert(axis, 0),
+            Format::Slices(slices) => slices.insert(axis, vec![0]),
+        }
+        view
+    }
+
+    /// Creates a view with the order of the elements along `axis` reversed. The data is not copied.
+    pub fn flip_axis(&self, axis: usize) -> NdarrayBase<T> {
+        let mut ranges = self
+            .shape
+            .iter()
+            .map(|&len| (0, len, 1))
+            .collect::<Vec<_>>();
+        let len = self.shape[axis];
+        ranges[axis] = (len.saturating_sub(1), len, -1);
+        self.slice_axes(&ranges)
+    }
+
+    /// Returns the number of elements of the array.
+    pub fn size(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Returns the position in `data` of the element with the given indices.
+    ///
+    /// The indices have to lie within `shape`.
+    pub fn data_index(&self, indices: &[usize]) -> usize {
+        format_index(self.offset, &self.strides, &self.format, indices)
+    }
+}
+
+/// Returns the position in the data of an array with the given base offset, strides and format of the element with the given indices.
+pub fn format_index(offset: usize, strides: &[isize], format: &Format, indices: &[usize]) -> usize {
+    let position: isize = match format {
+        Format::None => strides
+            .iter()
+            .zip(indices.iter())
+            .map(|(x, &y)| x * y as isize)
+            .sum(),
+        Format::Slice(offsets) => strides
+            .iter()
+            .zip(indices.iter())
+            .enumerate()
+            .map(|(i, (x, y))| x * (offsets[i] + y) as isize)
+            .sum(),
+        Format::Slices(slices) => strides
+            .iter()
+            .zip(slices.iter())
+            .enumerate()
+            .map(|(i, (x, y))| x * y[indices[i]] as isize)
+            .sum(),
+    };
+    (offset as isize + position) as usize
+}
+
+impl<T: Copy> NdarrayBase<T> {
+    /// Copies the elements of the array in row-major order into a new vector.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.data_indices().map(|i| self.data[i]).collect()
+    }
+
+    /// Creates an array with the same elements in row-major order and the given `shape`, which has to have the same number of elements.
+    ///
+    /// Arrays whose elements are stored contiguously become a view of the same memory, all others are copied.
+    pub fn reshaped(&self, shape: Vec<usize>) -> NdarrayBase<T> {
+        match self.contiguous_offset() {
+            Some(offset) => NdarrayBase {
+                data: self.data.clone(),
+                strides: Ndarray::get_strides_from_shape(&shape),
+                shape,
+                offset,
+                format: Format::None,
+            },
+            None => NdarrayBase::from_vec(self.to_vec(), shape),
+        }
+    }
+
+    /// Applies `f` to every element and returns the results as a new contigious array with the same shape.
+    pub fn map<U>(&self, f: impl Fn(T) -> U) -> NdarrayBase<U> {
+        NdarrayBase::from_vec(
+            self.data_indices().map(|i| f(self.data[i])).collect(),
+            self.shape.clone(),
+        )
+    }
+
+    /// Reorders the subarrays along `axis` in place according to `permutation`, which has a length of `shape[axis]`, so that position `i` afterwards holds the subarray that was at position `permutation[i]`.
+    ///
+    /// The data is only written if no other array shares it. Broadcast arrays, which repeat elements, can't be permuted.
+    pub fn permute_along(&mut self, axis: usize, permutation: &[usize]) -> Result<(), String> {
+        if self.size() == 0 {
+            return Ok(());
+        }
+        if self
+            .shape
+            .iter()
+            .zip(self.strides.iter())
+            .any(|(&len, &stride)| len > 1 && stride == 0)
+        {
+            return Err(String::from("Broadcast arrays can't be mutated."));
+        }
+        let NdarrayBase {
+            data,
+            shape,
+            strides,
+            offset,
+            format,
+        } = self;
+        let data = Rc::get_mut(data)
+            .ok_or_else(|| String::from("Data must have single owner to be mutated."))?;
+        let mut outer_shape = shape.clone();
+        outer_shape[axis] = 1;
+        let mut indices = vec![0; shape.len()];
+        loop {
+            let positions = (0..shape[axis])
+                .map(|i| {
+                    indices[axis] = i;
+                    format_index(*offset, strides, format, &indices)
+                })
+                .collect::<Vec<usize>>();
+            let lane = permutation
+                .iter()
+                .map(|&i| data[positions[i]])
+                .collect::<Vec<T>>();
+            for (&position, value) in positions.iter().zip(lane) {
+                data[position] = value;
+            }
+            indices[axis] = 0;
+            if !next_indices(&mut indices, &outer_shape) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Turns a possibly negative axis into the corresponding positive axis of an array with `ndim` dimensions.
+pub fn normalize_axis(axis: isize, ndim: usize) -> Result<usize, String> {
+    let normalized = if axis < 0 { axis + ndim as isize } else { axis };
+    if normalized >= 0 && normalized < ndim as isize {
+        Ok(normalized as usize)
+    } else {
+        Err(format!(
+            "Axis {} is out of bounds for array of dimension {}.",
+            axis, ndim
+        ))
+    }
+}
+
+/// Resolves a Python-style range of an axis with length `len` into `(start, len, step)` of the selected elements.
+///
+/// Missing bounds select the axis to its end in the direction of `step`, which defaults to one. Negative bounds are counted from the end of the axis and `stop` is exclusive. Bounds outside of `[-len, len]` and a step of zero are errors.
+pub fn normalize_range(
+    start: Option<isize>,
+    stop: Option<isize>,
+    step: Option<isize>,
+    len: usize,
+) -> Result<(usize, usize, isize), String> {
+    let n = len as isize;
+    // steps beyond the length select at most one element either way, clamping them keeps the arithmetic from overflowing
+    let step = step.unwrap_or(1).clamp(-n.max(1), n.max(1));
+    if step == 0 {
+        return Err(String::from("Slice step must not be zero."));
+    }
+    let bound = |x: isize| {
+        if x < -n || x > n {
+            Err(format!(
+                "Slice bound {} is out of bounds for axis with length {}.",
+                x, len
+            ))
+        } else if x < 0 {
+            Ok(x + n)
+        } else {
+            Ok(x)
+        }
+    };
+    // backwards ranges start at the last element and end before the first one
+    let (start, stop) = if step > 0 {
+        (start.map_or(Ok(0), bound)?, stop.map_or(Ok(n), bound)?)
+    } else {
+        (
+            start.map_or(Ok(n - 1), bound)?.min(n - 1),
+            stop.map_or(Ok(-1), bound)?,
+        )
+    };
+    // ceil((stop - start) / step) for both signs of the step
+    let count = (stop - start + step - step.signum()) / step;
+    Ok((start.max(0) as usize, count.max(0) as usize, step))
+}
+
+/// Advances `indices` to the next position of an array with the given shape in row-major order.
+///
+/// Returns `false` and resets the indices to zero after the last position.
+pub fn next_indices(indices: &mut [usize], shape: &[usize]) -> bool {
+    for (index, len) in indices.iter_mut().zip(shape.iter()).rev() {
+        *index += 1;
+        if *index < *len {
+            return true;
+        }
+        *index = 0;
+    }
+    false
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// Any javascript object with a `view` method, which is expected to be a Ndarray.
+    type JsNdarray;
+
+    #[wasm_bindgen(method, catch)]
+    fn view(this: &JsNdarray) -> Result<JsValue, JsValue>;
+}
+
+impl Ndarray {
+    /// Returns a Ndarray that shares the data of the javascript Ndarray `input` without taking ownership of the javascript object.
+    ///
+    /// wasm-bindgen only lends a Ndarray to rust when the parameter is declared as `&Ndarray`. Ndarrays in a `JsValue` that can also hold a number, in optional parameters or in javascript Arrays can only be moved into rust, which would leave the javascript object without its data. Such parameters are taken as `JsValue` and passed to this function, which calls the `view` method of the object and moves the new view instead.
+    pub(crate) fn borrow_js(input: &JsValue) -> Result<Ndarray, JsValue> {
+        let error = || JsValue::from_str("Expected a Ndarray.");
+        let view = input
+            .unchecked_ref::<JsNdarray>()
+            .view()
+            .map_err(|_| error())?;
+        Ndarray::try_from_js_value(view).map_err(|_| error())
+    }
+
+    /// Converts the result of a computation into a javascript value. Zero-dimensional results are returned as numbers, all others as Ndarray.
+    pub(crate) fn into_js_value(self) -> JsValue {
+        if self.shape().is_empty() {
+            dispatch!(&self.0, ndarray => ndarray.data[ndarray.data_index(&[])].to_js())
+        } else {
+            JsValue::from(self)
+        }
+    }
+
+    /// Copies the subarrays along the first axis with the given indices into a new Ndarray. The first axis of the result is replaced by the axes `shape`, which have to contain as many elements as `rows`.
+    pub fn take_rows(&self, rows: &[usize], shape: &[usize]) -> Ndarray {
+        let len = self.shape()[1..].iter().product::<usize>();
+        let mut result_shape = shape.to_vec();
+        result_shape.extend_from_slice(&self.shape()[1..]);
+        Ndarray(dispatch!(&self.0, ndarray => {
+            let data = ndarray.to_vec();
+            let data = rows
+                .iter()
+                .flat_map(|&row| data[row * len..(row + 1) * len].iter().copied())
+                .collect();
+            NdarrayBase::from_vec(data, result_shape).into_union()
+        }))
+    }
+
+    /// Reorders the subarrays along `axis` in place, so that position `i` afterwards holds the subarray that was at position `permutation[i]`. Fails if the data is shared with another Ndarray.
+    pub fn permute_along(&mut self, axis: usize, permutation: &[usize]) -> Result<(), JsValue> {
+        dispatch!(&mut self.0, ndarray => ndarray.permute_along(axis, permutation))
+            .map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Creates a one-dimensional Ndarray from the elements of a typed array.
+    fn from_typed_array<T: Element>(data: Vec<T>) -> Ndarray {
+        let len = data.len();
+        Ndarray(NdarrayBase::from_vec(data, vec![len]).into_union())
+    }
+
+    /// Creates a Ndarray from data that is stored contigiously in row-major order.
+    fn new_rust<T: Element>(input: Vec<T>, shape: Vec<usize>) -> Result<Ndarray, String> {
+        if input.len() != shape.iter().product::<usize>() {
+            return Err(String::from("Shape doesn't fit data."));
+        }
+        Ok(Ndarray(
+            NdarrayBase {
+                strides: Ndarray::get_strides_from_shape(&shape),
+                shape,
+                data: Rc::from(input),
+                offset: 0,
+                format: Format::None,
+            }
+            .into_union(),
+        ))
+    }
+
     /// Calculates the strides from a given shape.
-    fn get_strides_from_shape(shape: &Vec<usize>) -> Vec<usize> {
+    fn get_strides_from_shape(shape: &[usize]) -> Vec<isize> {
         let mut m = 1;
         shape
             .iter()
             .rev()
             .map(|x| {
                 let n = m;
-                m = m * x;
+                m *= *x as isize;
                 n
             })
-            .collect::<Vec<usize>>()
+            .collect::<Vec<isize>>()
             .into_iter()
             .rev()
             .collect()
     }
     /// Returns the field `strides` from a Ndarray.
-    pub fn strides(&self) -> &Vec<usize> {
-        match &self.0 {
-            NdarrayUnion::I32(ndarray) => &ndarray.strides,
-            NdarrayUnion::F64(ndarray) => &ndarray.strides,
-        }
+    pub fn strides(&self) -> &Vec<isize> {
+        dispatch!(&self.0, ndarray => &ndarray.strides)
     }
     /// Sets the field `strides` of the Ndarray according to the input.
-    pub fn set_strides(&mut self, strides: Vec<usize>) {
-        match &mut self.0 {
-            NdarrayUnion::I32(ndarray) => ndarray.strides = strides,
-            NdarrayUnion::F64(ndarray) => ndarray.strides = strides,
-        }
+    pub fn set_strides(&mut self, strides: Vec<isize>) {
+        dispatch!(&mut self.0, ndarray => ndarray.strides = strides)
     }
     /// Return the field `shape` of an array.
     pub fn shape(&self) -> &Vec<usize> {
-        match &self.0 {
-            NdarrayUnion::I32(ndarray) => &ndarray.shape,
-            NdarrayUnion::F64(ndarray) => &ndarray.shape,
-        }
+        dispatch!(&self.0, ndarray => &ndarray.shape)
     }
     /// Sets the field `strides` of the Ndarray according to the input.
     pub fn set_shape(&mut self, shape: Vec<usize>) {
-        match &mut self.0 {
-            NdarrayUnion::I32(ndarray) => ndarray.shape = shape,
-            NdarrayUnion::F64(ndarray) => ndarray.shape = shape,
-        }
+        dispatch!(&mut self.0, ndarray => ndarray.shape = shape)
     }
     /// Return the field `shape` of an array.
     pub fn format(&self) -> &Format {
-        match &self.0 {
-            NdarrayUnion::I32(ndarray) => &ndarray.format,
-            NdarrayUnion::F64(ndarray) => &ndarray.format,
-        }
+        dispatch!(&self.0, ndarray => &ndarray.format)
     }
 }
 
 #[wasm_bindgen]
 impl NdarrayMut {
     pub fn new(input: &mut Ndarray) -> Result<NdarrayMut, JsValue> {
-        match &mut input.0 {
-            NdarrayUnion::I32(ndarray) => match Rc::get_mut(&mut ndarray.data) {
-                Some(mut_ref) => Ok(NdarrayMut(NdarrayUnionMut::I32(NdarrayBaseMut {
+        fn base_mut<T>(ndarray: &mut NdarrayBase<T>) -> Result<NdarrayBaseMut<T>, JsValue> {
+            match Rc::get_mut(&mut ndarray.data) {
+                Some(mut_ref) => Ok(NdarrayBaseMut {
                     data: mut_ref,
                     shape: ndarray.shape.clone(),
                     strides: ndarray.strides.clone(),
-                    format: Format::None,
-                }))),
+                    offset: ndarray.offset,
+                    format: ndarray.format.clone(),
+                }),
                 None => Err(JsValue::from_str(
                     "Data must have single owner to be mutated.",
                 )),
-            },
-            NdarrayUnion::F64(ndarray) => match Rc::get_mut(&mut ndarray.data) {
-                Some(mut_ref) => Ok(NdarrayMut(NdarrayUnionMut::F64(NdarrayBaseMut {
-                    data: mut_ref,
-                    shape: ndarray.shape.clone(),
-                    strides: ndarray.strides.clone(),
-                    format: Format::None,
-                }))),
-                None => Err(JsValue::from_str(
-                    "Data must have single owner to be mutated.",
-                )),
-            },
+            }
         }
+        Ok(NdarrayMut(match &mut input.0 {
+            NdarrayUnion::Bool(ndarray) => NdarrayUnionMut::Bool(base_mut(ndarray)?),
+            NdarrayUnion::I8(ndarray) => NdarrayUnionMut::I8(base_mut(ndarray)?),
+            NdarrayUnion::U8(ndarray) => NdarrayUnionMut::U8(base_mut(ndarray)?),
+            NdarrayUnion::I16(ndarray) => NdarrayUnionMut::I16(base_mut(ndarray)?),
+            NdarrayUnion::U16(ndarray) => NdarrayUnionMut::U16(base_mut(ndarray)?),
+            NdarrayUnion::I32(ndarray) => NdarrayUnionMut::I32(base_mut(ndarray)?),
+            NdarrayUnion::U32(ndarray) => NdarrayUnionMut::U32(base_mut(ndarray)?),
+            NdarrayUnion::I64(ndarray) => NdarrayUnionMut::I64(base_mut(ndarray)?),
+            NdarrayUnion::U64(ndarray) => NdarrayUnionMut::U64(base_mut(ndarray)?),
+            NdarrayUnion::F16(ndarray) => NdarrayUnionMut::F16(base_mut(ndarray)?),
+            NdarrayUnion::BF16(ndarray) => NdarrayUnionMut::BF16(base_mut(ndarray)?),
+            NdarrayUnion::F32(ndarray) => NdarrayUnionMut::F32(base_mut(ndarray)?),
+            NdarrayUnion::F64(ndarray) => NdarrayUnionMut::F64(base_mut(ndarray)?),
+            NdarrayUnion::C64(ndarray) => NdarrayUnionMut::C64(base_mut(ndarray)?),
+            NdarrayUnion::C128(ndarray) => NdarrayUnionMut::C128(base_mut(ndarray)?),
+        }))
+    }
+}
+
+impl NdarrayMut {
+    /// Return the field `shape` of the array.
+    pub fn shape(&self) -> &Vec<usize> {
+        dispatch_mut!(&self.0, ndarray => &ndarray.shape)
     }
 }
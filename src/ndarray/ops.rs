@@ -0,0 +1,354 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Element-wise arithmetic of the element types of `NdarrayUnion`.
+///
+/// Integer operations wrap around on overflow, like the corresponding typed arrays in javascript.
+pub trait Arithmetic: Element + PartialOrd {
+    const ZERO: Self;
+    const ONE: Self;
+    fn to_f64(self) -> f64;
+    /// Converts a floating point number with the semantics of an `as` cast, which saturates at the bounds of integer types.
+    fn from_f64(x: f64) -> Self;
+    fn to_i128(self) -> i128;
+    /// Converts an integer with the semantics of an `as` cast, which truncates to the size of integer types.
+    fn from_i128(x: i128) -> Self;
+    fn to_complex(self) -> Complex<f64> {
+        Complex::new(self.to_f64(), 0.0)
+    }
+    /// Converts a complex number by discarding the imaginary part.
+    fn from_complex(x: Complex<f64>) -> Self {
+        Self::from_f64(x.re)
+    }
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+    fn mul(self, other: Self) -> Self;
+    fn div(self, other: Self) -> Self;
+    fn pow(self, other: Self) -> Self;
+    /// Remainder of the division, which has the same sign as the divisor.
+    fn rem(self, other: Self) -> Self;
+}
+
+macro_rules! impl_arithmetic_int {
+    ($($t:ty),*) => {
+        $(
+            impl Arithmetic for $t {
+                const ZERO: $t = 0;
+                const ONE: $t = 1;
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn from_f64(x: f64) -> $t {
+                    x as $t
+                }
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+                fn from_i128(x: i128) -> $t {
+                    x as $t
+                }
+                fn add(self, other: $t) -> $t {
+                    self.wrapping_add(other)
+                }
+                fn sub(self, other: $t) -> $t {
+                    self.wrapping_sub(other)
+                }
+                fn mul(self, other: $t) -> $t {
+                    self.wrapping_mul(other)
+                }
+                /// Integer division rounding towards negative infinity. Division by zero returns zero.
+                #[allow(unused_comparisons)]
+                fn div(self, other: $t) -> $t {
+                    if other == 0 {
+                        0
+                    } else {
+                        let quotient = self.wrapping_div(other);
+                        if self.wrapping_rem(other) != 0 && ((self < 0) != (other < 0)) {
+                            quotient - 1
+                        } else {
+                            quotient
+                        }
+                    }
+                }
+                /// Negative exponents truncate the result towards zero.
+                #[allow(unused_comparisons)]
+                fn pow(self, other: $t) -> $t {
+                    if other >= 0 {
+                        self.wrapping_pow(other as u32)
+                    } else if self == 1 {
+                        1
+                    } else if self.wrapping_add(1) == 0 {
+                        // -1 for signed types
+                        if other % 2 == 0 {
+                            1
+                        } else {
+                            self
+                        }
+                    } else {
+                        0
+                    }
+                }
+                /// Division by zero returns zero.
+                #[allow(unused_comparisons)]
+                fn rem(self, other: $t) -> $t {
+                    if other == 0 {
+                        0
+                    } else {
+                        let rem = self.wrapping_rem(other);
+                        if rem != 0 && ((rem < 0) != (other < 0)) {
+                            rem.wrapping_add(other)
+                        } else {
+                            rem
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_arithmetic_int!(i8, u8, i16, u16, i32, u32, i64, u64);
+
+macro_rules! impl_arithmetic_float {
+    ($($t:ty),*) => {
+        $(
+            impl Arithmetic for $t {
+                const ZERO: $t = 0.0;
+                const ONE: $t = 1.0;
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn from_f64(x: f64) -> $t {
+                    x as $t
+                }
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+                fn from_i128(x: i128) -> $t {
+                    x as $t
+                }
+                fn add(self, other: $t) -> $t {
+                    self + other
+                }
+                fn sub(self, other: $t) -> $t {
+                    self - other
+                }
+                fn mul(self, other: $t) -> $t {
+                    self * other
+                }
+                fn div(self, other: $t) -> $t {
+                    self / other
+                }
+                fn pow(self, other: $t) -> $t {
+                    self.powf(other)
+                }
+                fn rem(self, other: $t) -> $t {
+                    let rem = self % other;
+                    if rem != 0.0 && ((rem < 0.0) != (other < 0.0)) {
+                        rem + other
+                    } else {
+                        rem
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_arithmetic_float!(f32, f64);
+
+/// Booleans behave like integers that saturate at one. Binary operations on two boolean Ndarrays compute in `int8` instead, see `Ndarray::binary_op`.
+impl Arithmetic for bool {
+    const ZERO: bool = false;
+    const ONE: bool = true;
+    fn to_f64(self) -> f64 {
+        self as u8 as f64
+    }
+    /// Every number except zero is `true`.
+    fn from_f64(x: f64) -> bool {
+        x != 0.0
+    }
+    fn to_i128(self) -> i128 {
+        self as i128
+    }
+    /// Every number except zero is `true`.
+    fn from_i128(x: i128) -> bool {
+        x != 0
+    }
+    /// Every complex number except zero is `true`, also if only the imaginary part differs from zero.
+    fn from_complex(x: Complex<f64>) -> bool {
+        x.re != 0.0 || x.im != 0.0
+    }
+    fn add(self, other: bool) -> bool {
+        self | other
+    }
+    fn sub(self, other: bool) -> bool {
+        self & !other
+    }
+    fn mul(self, other: bool) -> bool {
+        self & other
+    }
+    fn div(self, other: bool) -> bool {
+        self & other
+    }
+    fn pow(self, other: bool) -> bool {
+        self | !other
+    }
+    fn rem(self, _: bool) -> bool {
+        false
+    }
+}
+
+/// Binary element-wise operations that can be applied to two Ndarrays.
+#[derive(Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    Rem,
+}
+
+impl BinaryOp {
+    /// Returns the function that computes the operation for two elements.
+    pub fn kernel<T: Arithmetic>(self) -> fn(T, T) -> T {
+        match self {
+            BinaryOp::Add => T::add,
+            BinaryOp::Sub => T::sub,
+            BinaryOp::Mul => T::mul,
+            BinaryOp::Div => T::div,
+            BinaryOp::Pow => T::pow,
+            BinaryOp::Rem => T::rem,
+        }
+    }
+}
+
+/// Applies `f` to the elements of `a` and `b` after broadcasting them to a common shape and returns the result as a new contigious array.
+pub fn zip_with<A: Copy, B: Copy, C>(
+    a: &NdarrayBase<A>,
+    b: &NdarrayBase<B>,
+    f: impl Fn(A, B) -> C,
+) -> Result<NdarrayBase<C>, String> {
+    let (a, b) = broadcast::broadcast_pair(a, b)?;
+    let data = a
+        .data_indices()
+        .zip(b.data_indices())
+        .map(|(i, j)| f(a.data[i], b.data[j]))
+        .collect();
+    Ok(NdarrayBase::from_vec(data, a.shape))
+}
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Adds `other`, which is a Ndarray or a number, element-wise. The shapes of both operands are broadcast against each other.
+    pub fn add(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.arithmetic(other, BinaryOp::Add)
+    }
+
+    /// Subtracts `other`, which is a Ndarray or a number, element-wise. The shapes of both operands are broadcast against each other.
+    pub fn sub(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.arithmetic(other, BinaryOp::Sub)
+    }
+
+    /// Multiplies with `other`, which is a Ndarray or a number, element-wise. The shapes of both operands are broadcast against each other.
+    pub fn mul(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.arithmetic(other, BinaryOp::Mul)
+    }
+
+    /// Divides by `other`, which is a Ndarray or a number, element-wise. The shapes of both operands are broadcast against each other.
+    ///
+    /// The division of two integer Ndarrays results in a floating point Ndarray.
+    pub fn div(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.arithmetic(other, BinaryOp::Div)
+    }
+
+    /// Raises the elements of the Ndarray to the powers given by `other`, which is a Ndarray or a number. The shapes of both operands are broadcast against each other.
+    pub fn pow(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.arithmetic(other, BinaryOp::Pow)
+    }
+
+    /// Computes the element-wise remainder of the division by `other`, which is a Ndarray or a number. The result has the same sign as the divisor.
+    pub fn rem(&self, other: &JsValue) -> Result<Ndarray, JsValue> {
+        self.arithmetic(other, BinaryOp::Rem)
+    }
+}
+
+impl Ndarray {
+    /// Applies a binary operation to the Ndarray and `other`, which is a Ndarray or a javascript number.
+    fn arithmetic(&self, other: &JsValue, op: BinaryOp) -> Result<Ndarray, JsValue> {
+        match other.as_f64() {
+            Some(number) => self.scalar_op(number, op),
+            None => {
+                let other = Ndarray::borrow_js(other)
+                    .map_err(|_| JsValue::from_str("Expected a Ndarray or a number."))?;
+                self.binary_op(&other, op)
+            }
+        }
+    }
+
+    /// Applies a binary operation to every element of the Ndarray and a javascript number.
+    ///
+    /// The number is weakly typed: it is converted to the element type of the Ndarray, so that for example adding one to a `uint8` Ndarray results in `uint8` values. Only fractional numbers move integer and boolean Ndarrays to `float64`, and integral numbers move boolean Ndarrays to `int32`. Integral numbers that don't fit into the integer element type are errors.
+    pub fn scalar_op(&self, number: f64, op: BinaryOp) -> Result<Ndarray, JsValue> {
+        let dtype = operation_dtype(scalar_dtype(self.0.dtype(), number), op)?;
+        let result = with_dtype!(dtype, T => {
+            let scalar = scalar_value::<T>(number, dtype)?;
+            let kernel = op.kernel::<T>();
+            self.0.convert::<T>().map(|x| kernel(x, scalar)).into_union()
+        });
+        Ok(Ndarray(result))
+    }
+
+    /// Applies a binary operation element-wise to two Ndarrays.
+    ///
+    /// Both operands are converted to the promoted type of their element types, see `DType::promote`. Two boolean operands are treated as `int8`. Division of integers results in `float64` values.
+    pub fn binary_op(&self, other: &Ndarray, op: BinaryOp) -> Result<Ndarray, JsValue> {
+        let dtype = operation_dtype(DType::promote(self.0.dtype(), other.0.dtype()), op)?;
+        let result = with_dtype!(dtype, T => zip_with(
+            &self.0.convert::<T>(),
+            &other.0.convert::<T>(),
+            op.kernel::<T>(),
+        )
+        .map(NdarrayBase::into_union));
+        result.map(Ndarray).map_err(|err| JsValue::from_str(&err))
+    }
+}
+
+/// Returns the element type to which a Ndarray of element type `dtype` and a weakly typed javascript number are converted. Only fractional numbers move integer and boolean Ndarrays to `float64`, and integral numbers move boolean Ndarrays to `int32`.
+pub fn scalar_dtype(dtype: DType, number: f64) -> DType {
+    match dtype {
+        _ if dtype.is_float() || dtype.is_complex() => dtype,
+        _ if number.fract() != 0.0 => DType::F64,
+        DType::Bool => DType::I32,
+        _ => dtype,
+    }
+}
+
+/// Converts a javascript number to the element type `T` of `dtype`. Integral numbers that don't fit into an integer type are errors.
+pub fn scalar_value<T: Arithmetic>(number: f64, dtype: DType) -> Result<T, JsValue> {
+    let scalar = T::from_f64(number);
+    if !dtype.is_float() && !dtype.is_complex() && scalar.to_i128() != number as i128 {
+        return Err(JsValue::from_str(&format!(
+            "Number {} is out of bounds for dtype {}.",
+            number,
+            dtype.name()
+        )));
+    }
+    Ok(scalar)
+}
+
+/// Returns the element type in which a binary operation on operands of the element type `dtype` is computed. Booleans compute in `int8` and the division of integers in `float64`.
+fn operation_dtype(dtype: DType, op: BinaryOp) -> Result<DType, JsValue> {
+    if op == BinaryOp::Rem && dtype.is_complex() {
+        return Err(JsValue::from_str(
+            "The remainder is not defined for complex numbers.",
+        ));
+    }
+    Ok(match dtype {
+        _ if op == BinaryOp::Div && !dtype.is_float() && !dtype.is_complex() => DType::F64,
+        DType::Bool => DType::I8,
+        _ => dtype,
+    })
+}
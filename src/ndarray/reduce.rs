@@ -0,0 +1,263 @@
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Reductions that combine the elements along one or more axes of a Ndarray.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Reduction {
+    Sum,
+    Prod,
+    Mean,
+    Min,
+    Max,
+    ArgMin,
+    ArgMax,
+    Any,
+    All,
+}
+
+/// Applies `f` to every lane of elements along `axes` and returns the results in an array with the remaining axes.
+///
+/// The elements of a lane are passed in row-major order of the reduced axes. Reduced axes are removed from the shape, or kept with length one if `keepdims` is set.
+pub fn reduce_axes<T: Copy, U>(
+    ndarray: &NdarrayBase<T>,
+    axes: &[usize],
+    keepdims: bool,
+    f: impl Fn(&[T]) -> U,
+) -> NdarrayBase<U> {
+    let ndim = ndarray.shape.len();
+    let kept = (0..ndim)
+        .filter(|i| !axes.contains(i))
+        .collect::<Vec<usize>>();
+    // moving the reduced axes to the end makes every lane a contigious chunk
+    let order = kept
+        .iter()
+        .chain(axes.iter())
+        .cloned()
+        .collect::<Vec<usize>>();
+    let data = ndarray.permute_axes(&order).to_vec();
+    let lane = axes.iter().map(|&i| ndarray.shape[i]).product::<usize>();
+    let size = kept.iter().map(|&i| ndarray.shape[i]).product::<usize>();
+    let shape = if keepdims {
+        (0..ndim)
+            .map(|i| {
+                if axes.contains(&i) {
+                    1
+                } else {
+                    ndarray.shape[i]
+                }
+            })
+            .collect()
+    } else {
+        kept.iter().map(|&i| ndarray.shape[i]).collect()
+    };
+    let result = (0..size)
+        .map(|k| f(&data[k * lane..(k + 1) * lane]))
+        .collect();
+    NdarrayBase::from_vec(result, shape)
+}
+
+/// Returns the position of the first minimum (or maximum) of a non-empty lane. NaN values take precedence over all other values.
+fn arg_extreme<T: PartialOrd>(lane: &[T], max: bool) -> usize {
+    let mut best = 0;
+    for (i, x) in lane.iter().enumerate() {
+        if x.partial_cmp(x).is_none() {
+            return i;
+        }
+        if (max && *x > lane[best]) || (!max && *x < lane[best]) {
+            best = i;
+        }
+    }
+    best
+}
+
+/// Computes a reduction over the given axes of a NdarrayBase.
+///
+/// Sum, product, minimum and maximum keep the element type. The mean is always floating point (`complex128` for complex arrays), the positions of argmin and argmax are integers and `any` and `all` are booleans.
+fn reduce_base<T: Arithmetic>(
+    ndarray: &NdarrayBase<T>,
+    reduction: Reduction,
+    axes: &[usize],
+    keepdims: bool,
+) -> Result<NdarrayUnion, String> {
+    let wrap = NdarrayBase::<T>::into_union;
+    let lane = axes.iter().map(|&i| ndarray.shape[i]).product::<usize>();
+    match reduction {
+        Reduction::Min | Reduction::Max | Reduction::ArgMin | Reduction::ArgMax if lane == 0 => {
+            return Err(String::from(
+                "Zero-size lanes can't be reduced with an operation that has no identity.",
+            ))
+        }
+        _ => (),
+    }
+    Ok(match reduction {
+        Reduction::Sum => wrap(reduce_axes(ndarray, axes, keepdims, |lane| {
+            lane.iter().fold(T::ZERO, |acc, &x| acc.add(x))
+        })),
+        Reduction::Prod => wrap(reduce_axes(ndarray, axes, keepdims, |lane| {
+            lane.iter().fold(T::ONE, |acc, &x| acc.mul(x))
+        })),
+        Reduction::Mean if T::DTYPE.is_complex() => {
+            NdarrayUnion::C128(reduce_axes(ndarray, axes, keepdims, |lane| {
+                let sum = lane
+                    .iter()
+                    .fold(Complex::new(0.0, 0.0), |acc, x| acc.add(x.to_complex()));
+                Complex::new(sum.re / lane.len() as f64, sum.im / lane.len() as f64)
+            }))
+        }
+        Reduction::Mean => NdarrayUnion::F64(reduce_axes(ndarray, axes, keepdims, |lane| {
+            lane.iter().map(|x| x.to_f64()).sum::<f64>() / lane.len() as f64
+        })),
+        Reduction::Min => wrap(reduce_axes(ndarray, axes, keepdims, |lane| {
+            lane[arg_extreme(lane, false)]
+        })),
+        Reduction::Max => wrap(reduce_axes(ndarray, axes, keepdims, |lane| {
+            lane[arg_extreme(lane, true)]
+        })),
+        Reduction::ArgMin => NdarrayUnion::I32(reduce_axes(ndarray, axes, keepdims, |lane| {
+            arg_extreme(lane, false) as i32
+        })),
+        Reduction::ArgMax => NdarrayUnion::I32(reduce_axes(ndarray, axes, keepdims, |lane| {
+            arg_extreme(lane, true) as i32
+        })),
+        Reduction::Any => NdarrayUnion::Bool(reduce_axes(ndarray, axes, keepdims, |lane| {
+            lane.iter().any(|&x| x != T::ZERO)
+        })),
+        Reduction::All => NdarrayUnion::Bool(reduce_axes(ndarray, axes, keepdims, |lane| {
+            lane.iter().all(|&x| x != T::ZERO)
+        })),
+    })
+}
+
+/// Turns the javascript axis argument of a reduction or of `flip` into a sorted list of axes.
+///
+/// `undefined` and `null` select all axes, a number selects a single axis and an Array of numbers selects multiple axes. Negative axes are counted from the end.
+pub fn parse_axes(axis: &JsValue, ndim: usize) -> Result<Vec<usize>, JsValue> {
+    let axes = if axis.is_undefined() || axis.is_null() {
+        return Ok((0..ndim).collect());
+    } else if let Some(number) = axis.as_f64() {
+        vec![number as isize]
+    } else if js_sys::Array::is_array(axis) {
+        js_interop::into_vec_isize(&js_sys::Array::from(axis))?
+    } else {
+        return Err(JsValue::from_str(
+            "Axis must be a number or an Array of numbers.",
+        ));
+    };
+    let mut axes = axes
+        .into_iter()
+        .map(|x| normalize_axis(x, ndim))
+        .collect::<Result<Vec<usize>, String>>()
+        .map_err(|err| JsValue::from_str(&err))?;
+    axes.sort_unstable();
+    let len = axes.len();
+    axes.dedup();
+    if axes.len() == len {
+        Ok(axes)
+    } else {
+        Err(JsValue::from_str("Axes must not contain duplicates."))
+    }
+}
+
+#[wasm_bindgen]
+impl Ndarray {
+    /// Sum of the elements over the given axis or Array of axes, or over all elements if no axis is given.
+    ///
+    /// Returns a number if all axes are reduced and `keepdims` isn't set, otherwise a Ndarray.
+    pub fn sum(&self, axis: JsValue, keepdims: Option<bool>) -> Result<JsValue, JsValue> {
+        self.reduce(Reduction::Sum, &axis, keepdims)
+    }
+
+    /// Product of the elements over the given axis or Array of axes, or over all elements if no axis is given.
+    ///
+    /// Returns a number if all axes are reduced and `keepdims` isn't set, otherwise a Ndarray.
+    pub fn prod(&self, axis: JsValue, keepdims: Option<bool>) -> Result<JsValue, JsValue> {
+        self.reduce(Reduction::Prod, &axis, keepdims)
+    }
+
+    /// Arithmetic mean of the elements over the given axis or Array of axes, or over all elements if no axis is given.
+    ///
+    /// Returns a number if all axes are reduced and `keepdims` isn't set, otherwise a Ndarray.
+    pub fn mean(&self, axis: JsValue, keepdims: Option<bool>) -> Result<JsValue, JsValue> {
+        self.reduce(Reduction::Mean, &axis, keepdims)
+    }
+
+    /// Minimum of the elements over the given axis or Array of axes, or over all elements if no axis is given. NaN values are propagated.
+    ///
+    /// Returns a number if all axes are reduced and `keepdims` isn't set, otherwise a Ndarray.
+    pub fn min(&self, axis: JsValue, keepdims: Option<bool>) -> Result<JsValue, JsValue> {
+        self.reduce(Reduction::Min, &axis, keepdims)
+    }
+
+    /// Maximum of the elements over the given axis or Array of axes, or over all elements if no axis is given. NaN values are propagated.
+    ///
+    /// Returns a number if all axes are reduced and `keepdims` isn't set, otherwise a Ndarray.
+    pub fn max(&self, axis: JsValue, keepdims: Option<bool>) -> Result<JsValue, JsValue> {
+        self.reduce(Reduction::Max, &axis, keepdims)
+    }
+
+    /// Position of the first minimum along the given axis. Without an axis the position refers to the flattened Ndarray.
+    ///
+    /// Returns a number if no axis is given and `keepdims` isn't set, otherwise a Ndarray.
+    pub fn argmin(&self, axis: JsValue, keepdims: Option<bool>) -> Result<JsValue, JsValue> {
+        self.reduce(Reduction::ArgMin, &axis, keepdims)
+    }
+
+    /// Position of the first maximum along the given axis. Without an axis the position refers to the flattened Ndarray.
+    ///
+    /// Returns a number if no axis is given and `keepdims` isn't set, otherwise a Ndarray.
+    pub fn argmax(&self, axis: JsValue, keepdims: Option<bool>) -> Result<JsValue, JsValue> {
+        self.reduce(Reduction::ArgMax, &axis, keepdims)
+    }
+
+    /// Tests if any element over the given axis or Array of axes is not zero, or any element at all if no axis is given.
+    ///
+    /// Returns a boolean if all axes are reduced and `keepdims` isn't set, otherwise a Ndarray.
+    pub fn any(&self, axis: JsValue, keepdims: Option<bool>) -> Result<JsValue, JsValue> {
+        self.reduce(Reduction::Any, &axis, keepdims)
+    }
+
+    /// Tests if all elements over the given axis or Array of axes are not zero, or all elements if no axis is given.
+    ///
+    /// Returns a boolean if all axes are reduced and `keepdims` isn't set, otherwise a Ndarray.
+    pub fn all(&self, axis: JsValue, keepdims: Option<bool>) -> Result<JsValue, JsValue> {
+        self.reduce(Reduction::All, &axis, keepdims)
+    }
+}
+
+impl Ndarray {
+    /// Computes a reduction over the axes given by the javascript axis argument.
+    fn reduce(
+        &self,
+        reduction: Reduction,
+        axis: &JsValue,
+        keepdims: Option<bool>,
+    ) -> Result<JsValue, JsValue> {
+        let axes = parse_axes(axis, self.shape().len())?;
+        let keepdims = keepdims.unwrap_or(false);
+        if (reduction == Reduction::ArgMin || reduction == Reduction::ArgMax)
+            && js_sys::Array::is_array(axis)
+            && axes.len() != 1
+        {
+            return Err(JsValue::from_str(
+                "Argmin and argmax can only be computed along a single axis.",
+            ));
+        }
+        // sums and products of booleans and small integers are accumulated in 32 bits, so they don't overflow as easily
+        let result = match (reduction, self.0.dtype()) {
+            (Reduction::Sum | Reduction::Prod, DType::I8 | DType::I16) => {
+                reduce_base(&self.0.convert::<i32>(), reduction, &axes, keepdims)
+            }
+            (Reduction::Sum | Reduction::Prod, DType::Bool | DType::U8 | DType::U16) => {
+                reduce_base(&self.0.convert::<u32>(), reduction, &axes, keepdims)
+            }
+            // half-precision floats don't have enough precision for long sums
+            (Reduction::Sum | Reduction::Prod, DType::F16 | DType::BF16) => {
+                reduce_base(&self.0.convert::<f32>(), reduction, &axes, keepdims)
+            }
+            _ => dispatch!(&self.0, ndarray => reduce_base(ndarray, reduction, &axes, keepdims)),
+        }
+        .map_err(|err| JsValue::from_str(&err))?;
+        Ok(Ndarray(result).into_js_value())
+    }
+}
@@ -1,15 +1,31 @@
 #![cfg(target_arch = "wasm32")]
 
+use wasm_bindgen::convert::TryFromJsValue;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
 
 use crate::ndarray::*;
 
 wasm_bindgen_test_configure!(run_in_browser);
+
+/// Creates a slice definition in which `None` becomes `null`.
+fn range(entries: &[Option<isize>]) -> JsValue {
+    entries
+        .iter()
+        .map(|x| match x {
+            Some(x) => JsValue::from(*x as f64),
+            None => JsValue::NULL,
+        })
+        .collect::<js_sys::Array>()
+        .into()
+}
+
 #[wasm_bindgen_test]
 fn test_new_reshape_get() {
     let input = (1..28).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
-    let mut ndarray = Ndarray::new(JsValue::from(js_interop::vec_f64_into_float64array(input)));
+    let mut ndarray = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
     let shape = js_interop::vec_isize_into_array(vec![3, 3, 3]);
     ndarray.reshape(&shape).unwrap();
     assert_eq!(
@@ -25,10 +41,12 @@ fn test_new_reshape_get() {
 #[wasm_bindgen_test]
 fn test_slice() {
     let input = (1..28).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
-    let mut ndarray = Ndarray::new(JsValue::from(js_interop::vec_f64_into_float64array(input)));
+    let mut ndarray = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
     let shape = js_interop::vec_isize_into_array(vec![3, 3, 3]);
     ndarray.reshape(&shape).unwrap();
-    match ndarray.slice(js_interop::vecvec_isize_into_arrayarray(vec![
+    match ndarray.slice(js_interop::test::vecvec_isize_into_arrayarray(vec![
         vec![1, 3],
         vec![0, 2],
         vec![0, 1],
@@ -51,10 +69,12 @@ fn test_slice() {
 #[wasm_bindgen_test]
 fn test_slices() {
     let input = (1..28).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
-    let mut ndarray = Ndarray::new(JsValue::from(js_interop::vec_f64_into_float64array(input)));
+    let mut ndarray = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
     let shape = js_interop::vec_isize_into_array(vec![3, 3, 3]);
     ndarray.reshape(&shape).unwrap();
-    match ndarray.slices(js_interop::vecvec_isize_into_arrayarray(vec![
+    match ndarray.slices(js_interop::test::vecvec_isize_into_arrayarray(vec![
         vec![0, 2],
         vec![0, 2],
         vec![0, 2],
@@ -73,3 +93,1064 @@ fn test_slices() {
         }
     }
 }
+
+#[wasm_bindgen_test]
+fn test_add_broadcast() {
+    let input = (1..7).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
+    let mut a = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
+    a.reshape(&js_interop::vec_isize_into_array(vec![2, 3]))
+        .unwrap();
+    let mut b = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        vec![10.0, 20.0],
+    )));
+    b.reshape(&js_interop::vec_isize_into_array(vec![2, 1]))
+        .unwrap();
+    let sum = a.add(&JsValue::from(b.view())).unwrap();
+    assert_eq!(sum.shape(), &vec![2, 3]);
+    assert_eq!(
+        sum.get(js_interop::vec_isize_into_array(vec![1, 2]))
+            .unwrap()
+            .as_f64()
+            .unwrap(),
+        26.0
+    );
+    assert!(a
+        .add(&JsValue::from(Ndarray::new(JsValue::from(
+            js_interop::test::vec_f64_into_float64array(vec![1.0, 2.0])
+        ))))
+        .is_err());
+    assert!(a.add(&JsValue::from_str("1")).is_err());
+    // the javascript operand keeps its data and can be used again
+    let b = JsValue::from(b);
+    a.add(&b).unwrap();
+    assert_eq!(a.add(&b).unwrap().shape(), &vec![2, 3]);
+}
+
+#[wasm_bindgen_test]
+fn test_scalar_ops_on_slice() {
+    let input = (1..28).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
+    let mut ndarray = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
+    let shape = js_interop::vec_isize_into_array(vec![3, 3, 3]);
+    ndarray.reshape(&shape).unwrap();
+    let slice = ndarray
+        .slice(js_interop::test::vecvec_isize_into_arrayarray(vec![
+            vec![1, 2],
+            vec![0, 2],
+            vec![0, 1],
+        ]))
+        .unwrap();
+    let result = slice
+        .mul(&JsValue::from(2.0))
+        .unwrap()
+        .rem(&JsValue::from(7.0))
+        .unwrap();
+    assert_eq!(
+        result
+            .get(js_interop::vec_isize_into_array(vec![0, 1, 0]))
+            .unwrap()
+            .as_f64()
+            .unwrap(),
+        5.0
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_integer_ops() {
+    let a = Ndarray::new(JsValue::from(js_sys::Int32Array::from(&[-7, 2, 9][..])));
+    let b = Ndarray::new(JsValue::from(js_sys::Int32Array::from(&[2, 2, 4][..])));
+    let rem = a.rem(&JsValue::from(b.view())).unwrap();
+    assert_eq!(
+        rem.get(js_interop::vec_isize_into_array(vec![0]))
+            .unwrap()
+            .as_f64()
+            .unwrap(),
+        1.0
+    );
+    let quotient = a.div(&JsValue::from(b.view())).unwrap();
+    assert_eq!(
+        quotient
+            .get(js_interop::vec_isize_into_array(vec![2]))
+            .unwrap()
+            .as_f64()
+            .unwrap(),
+        2.25
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_dtypes() {
+    let a = Ndarray::new(JsValue::from(js_sys::Uint8Array::from(&[250, 3, 7][..])));
+    let b = Ndarray::new(JsValue::from(js_sys::Int8Array::from(&[10, -3, 2][..])));
+    let f = Ndarray::new(JsValue::from(js_sys::Float32Array::from(
+        &[0.5, 1.5, 2.5][..],
+    )));
+    assert_eq!(a.dtype(), "uint8");
+    assert_eq!(f.dtype(), "float32");
+    assert_eq!(a.add(&JsValue::from(a.view())).unwrap().dtype(), "uint8");
+    let sum = a.add(&JsValue::from(b.view())).unwrap();
+    assert_eq!(sum.dtype(), "int16");
+    assert_eq!(
+        js_sys::Int16Array::from(sum.to_typed_array()).to_vec(),
+        vec![260, 0, 9]
+    );
+    assert_eq!(a.mul(&JsValue::from(f.view())).unwrap().dtype(), "float32");
+    assert_eq!(a.div(&JsValue::from(a.view())).unwrap().dtype(), "float64");
+    assert_eq!(
+        js_sys::Float32Array::from(f.to_typed_array()).to_vec(),
+        vec![0.5, 1.5, 2.5]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_int64() {
+    let big = (1i64 << 60) + 3;
+    let a = Ndarray::new(JsValue::from(js_sys::BigInt64Array::from(&[big, -5][..])));
+    let b = Ndarray::new(JsValue::from(js_sys::Uint32Array::from(
+        &[1, 4000000000][..],
+    )));
+    assert_eq!(a.dtype(), "int64");
+    let value = a.get(js_interop::vec_isize_into_array(vec![0])).unwrap();
+    assert!(value.is_bigint());
+    assert_eq!(js_sys::BigInt::from(value), big);
+    let sum = a.add(&JsValue::from(b.view())).unwrap();
+    assert_eq!(sum.dtype(), "int64");
+    assert_eq!(
+        js_sys::BigInt64Array::from(sum.to_typed_array()).to_vec(),
+        vec![big + 1, 3999999995]
+    );
+    let c = Ndarray::new(JsValue::from(js_sys::BigUint64Array::from(&[u64::MAX][..])));
+    assert_eq!(c.dtype(), "uint64");
+    assert_eq!(
+        js_sys::BigInt::from(c.get(js_interop::vec_isize_into_array(vec![0])).unwrap()),
+        u64::MAX
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_scalar_dtypes() {
+    let a = Ndarray::new(JsValue::from(js_sys::Uint8Array::from(&[250, 3, 7][..])));
+    let sum = a.add(&JsValue::from(1.0)).unwrap();
+    assert_eq!(sum.dtype(), "uint8");
+    assert_eq!(
+        js_sys::Uint8Array::from(sum.to_typed_array()).to_vec(),
+        vec![251, 4, 8]
+    );
+    // integer arithmetic wraps around like NumPy
+    let sum = a.add(&JsValue::from(10.0)).unwrap();
+    assert_eq!(
+        js_sys::Uint8Array::from(sum.to_typed_array()).to_vec(),
+        vec![4, 13, 17]
+    );
+    let product = a.mul(&JsValue::from(0.5)).unwrap();
+    assert_eq!(product.dtype(), "float64");
+    assert_eq!(
+        js_sys::Float64Array::from(product.to_typed_array()).to_vec(),
+        vec![125.0, 1.5, 3.5]
+    );
+    assert!(a.add(&JsValue::from(-1.0)).is_err());
+    assert!(a.add(&JsValue::from(256.0)).is_err());
+
+    let f = Ndarray::new(JsValue::from(js_sys::Float32Array::from(
+        &[0.5, 1.5, 2.5][..],
+    )));
+    let product = f.mul(&JsValue::from(2.0)).unwrap();
+    assert_eq!(product.dtype(), "float32");
+    assert_eq!(
+        js_sys::Float32Array::from(product.to_typed_array()).to_vec(),
+        vec![1.0, 3.0, 5.0]
+    );
+    let product = f.mul(&JsValue::from(0.5)).unwrap();
+    assert_eq!(product.dtype(), "float32");
+    assert_eq!(
+        js_sys::Float32Array::from(product.to_typed_array()).to_vec(),
+        vec![0.25, 0.75, 1.25]
+    );
+
+    // 1.0, -2.0, 0.5 and 65504.0 in float16
+    let bits = js_sys::Uint16Array::from(&[0x3c00, 0xc000, 0x3800, 0x7bff][..]);
+    let h = Ndarray::from_uint16_bits(&bits, "float16").unwrap();
+    let sum = h.add(&JsValue::from(1.0)).unwrap();
+    assert_eq!(sum.dtype(), "float16");
+    // 65504 + 1 rounds back to 65504
+    assert_eq!(
+        js_sys::Uint16Array::from(sum.to_typed_array()).to_vec(),
+        vec![0x4000, 0xbc00, 0x3e00, 0x7bff]
+    );
+
+    let big = (1u64 << 60) + 3;
+    let u = Ndarray::new(JsValue::from(js_sys::BigUint64Array::from(
+        &[big, u64::MAX - 1][..],
+    )));
+    let sum = u.add(&JsValue::from(1.0)).unwrap();
+    assert_eq!(sum.dtype(), "uint64");
+    assert_eq!(
+        js_sys::BigUint64Array::from(sum.to_typed_array()).to_vec(),
+        vec![big + 1, u64::MAX]
+    );
+    assert_eq!(u.div(&JsValue::from(2.0)).unwrap().dtype(), "float64");
+}
+
+#[wasm_bindgen_test]
+fn test_comparisons() {
+    let input = (1..7).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
+    let mut a = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
+    a.reshape(&js_interop::vec_isize_into_array(vec![2, 3]))
+        .unwrap();
+    let b = Ndarray::new(JsValue::from(js_sys::Int32Array::from(&[2, 2, 6][..])));
+    let mask = a.gt(&JsValue::from(b.view())).unwrap();
+    assert_eq!(mask.dtype(), "bool");
+    assert_eq!(
+        js_sys::Uint8Array::from(mask.to_typed_array()).to_vec(),
+        vec![0, 0, 0, 1, 1, 0]
+    );
+    let equal = a.eq(&JsValue::from(b.view())).unwrap();
+    assert_eq!(
+        equal
+            .get(js_interop::vec_isize_into_array(vec![1, 2]))
+            .unwrap()
+            .as_bool(),
+        Some(true)
+    );
+    let either = mask.logical_or(&equal).unwrap();
+    assert_eq!(
+        either.sum(JsValue::undefined(), None).unwrap().as_f64(),
+        Some(4.0)
+    );
+    assert_eq!(
+        mask.logical_not()
+            .logical_and(&either)
+            .unwrap()
+            .any(JsValue::undefined(), None)
+            .unwrap()
+            .as_bool(),
+        Some(true)
+    );
+    assert_eq!(
+        mask.all(JsValue::undefined(), None).unwrap().as_bool(),
+        Some(false)
+    );
+    let rows = mask.any(JsValue::from_f64(1.0), None).unwrap();
+    let rows = Ndarray::try_from_js_value(rows).unwrap();
+    assert_eq!(
+        js_sys::Uint8Array::from(rows.to_typed_array()).to_vec(),
+        vec![0, 1]
+    );
+
+    let c = Ndarray::new(JsValue::from(js_sys::Uint8Array::from(&[1, 2, 3][..])));
+    let less = c.lt(&JsValue::from_f64(2.5)).unwrap();
+    assert_eq!(less.dtype(), "bool");
+    assert_eq!(
+        js_sys::Uint8Array::from(less.to_typed_array()).to_vec(),
+        vec![1, 1, 0]
+    );
+    assert_eq!(
+        js_sys::Uint8Array::from(c.ge(&JsValue::from_f64(2.0)).unwrap().to_typed_array()).to_vec(),
+        vec![0, 1, 1]
+    );
+    assert!(c.lt(&JsValue::from_f64(256.0)).is_err());
+    assert_eq!(
+        js_sys::Uint8Array::from(mask.ne(&JsValue::from_f64(1.0)).unwrap().to_typed_array())
+            .to_vec(),
+        vec![1, 1, 1, 0, 0, 1]
+    );
+    assert!(c.eq(&JsValue::from_str("1")).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_mask_where() {
+    let input = (1..7).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
+    let mut a = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
+    a.reshape(&js_interop::vec_isize_into_array(vec![2, 3]))
+        .unwrap();
+    let mask = a.gt(&JsValue::from_f64(3.0)).unwrap();
+    let selected = a.mask(&mask).unwrap();
+    assert_eq!(selected.shape(), &vec![3]);
+    assert_eq!(
+        js_sys::Float64Array::from(selected.to_typed_array()).to_vec(),
+        vec![4.0, 5.0, 6.0]
+    );
+    let mut b = Ndarray::new(JsValue::from(js_sys::Int32Array::from(&[0, 10][..])));
+    assert!(a.mask(&b).is_err());
+    b.reshape(&js_interop::vec_isize_into_array(vec![2, 1]))
+        .unwrap();
+    let result = Ndarray::select(&mask, &a, &b).unwrap();
+    assert_eq!(result.dtype(), "float64");
+    assert_eq!(
+        js_sys::Float64Array::from(result.to_typed_array()).to_vec(),
+        vec![0.0, 0.0, 0.0, 4.0, 5.0, 6.0]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_complex() {
+    let re = Ndarray::new(JsValue::from(js_sys::Float32Array::from(&[3.0, -1.0][..])));
+    let im = Ndarray::new(JsValue::from(js_sys::Float32Array::from(&[4.0, 0.0][..])));
+    let z = Ndarray::complex(&re, &im).unwrap();
+    assert_eq!(z.dtype(), "complex64");
+    assert_eq!(
+        js_sys::Float32Array::from(z.to_typed_array()).to_vec(),
+        vec![3.0, 4.0, -1.0, 0.0]
+    );
+    let first = z.get(js_interop::vec_isize_into_array(vec![0])).unwrap();
+    assert_eq!(
+        js_sys::Reflect::get(&first, &JsValue::from_str("im"))
+            .unwrap()
+            .as_f64(),
+        Some(4.0)
+    );
+    assert_eq!(
+        js_sys::Float32Array::from(z.abs().to_typed_array()).to_vec(),
+        vec![5.0, 1.0]
+    );
+    assert_eq!(
+        js_sys::Float32Array::from(z.conj().imag().to_typed_array()).to_vec(),
+        vec![-4.0, 0.0]
+    );
+    let angle = z.angle();
+    assert_eq!(angle.dtype(), "float32");
+    let square = z.mul(&JsValue::from(z.view())).unwrap();
+    assert_eq!(
+        js_sys::Float32Array::from(square.to_typed_array()).to_vec(),
+        vec![-7.0, 24.0, 1.0, 0.0]
+    );
+    assert_eq!(
+        js_sys::Float32Array::from(
+            square
+                .div(&JsValue::from(z.view()))
+                .unwrap()
+                .real()
+                .to_typed_array()
+        )
+        .to_vec(),
+        vec![3.0, -1.0]
+    );
+    assert!(z.rem(&JsValue::from(z.view())).is_err());
+
+    // 0 and 1j
+    let zeros = Ndarray::zeros(&js_interop::vec_isize_into_array(vec![2]), None).unwrap();
+    let unit = Ndarray::new(JsValue::from(js_sys::Float64Array::from(&[0.0, 1.0][..])));
+    let j = Ndarray::complex(&zeros, &unit).unwrap();
+    let mask = j.astype("bool", None, None).unwrap();
+    assert_eq!(
+        js_sys::Uint8Array::from(mask.to_typed_array()).to_vec(),
+        vec![0, 1]
+    );
+    // vdot conjugates the first operand, so that 1j * 1j sums to one
+    let product = j.vdot(&j).unwrap();
+    let part = |name: &str| {
+        js_sys::Reflect::get(&product, &JsValue::from_str(name))
+            .unwrap()
+            .as_f64()
+    };
+    assert_eq!((part("re"), part("im")), (Some(1.0), Some(0.0)));
+    let dot = j.dot(&j).unwrap();
+    assert_eq!(
+        js_sys::Reflect::get(&dot, &JsValue::from_str("re"))
+            .unwrap()
+            .as_f64(),
+        Some(-1.0)
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_half_precision() {
+    // 1.0, -2.0, 0.5 and 65504.0 in float16
+    let bits = js_sys::Uint16Array::from(&[0x3c00, 0xc000, 0x3800, 0x7bff][..]);
+    let a = Ndarray::from_uint16_bits(&bits, "float16").unwrap();
+    assert_eq!(a.dtype(), "float16");
+    assert_eq!(
+        a.get(js_interop::vec_isize_into_array(vec![3]))
+            .unwrap()
+            .as_f64(),
+        Some(65504.0)
+    );
+    let sum = a.add(&JsValue::from(a.view())).unwrap();
+    assert_eq!(sum.dtype(), "float16");
+    // 65504 + 65504 overflows to infinity
+    assert_eq!(
+        js_sys::Uint16Array::from(sum.to_typed_array()).to_vec(),
+        vec![0x4000, 0xc400, 0x3c00, 0x7c00]
+    );
+    let b = Ndarray::from_uint16_bits(&bits, "bfloat16").unwrap();
+    assert_eq!(
+        b.get(js_interop::vec_isize_into_array(vec![0]))
+            .unwrap()
+            .as_f64(),
+        Some(0.0078125)
+    );
+    assert_eq!(a.add(&JsValue::from(b.view())).unwrap().dtype(), "float32");
+    assert!(Ndarray::from_uint16_bits(&bits, "int16").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_astype() {
+    let a = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        vec![2.5, -2.5, 3.7, 300.0, f64::NAN],
+    )));
+    let truncated = a.astype("int8", None, None).unwrap();
+    assert_eq!(truncated.dtype(), "int8");
+    assert_eq!(
+        js_sys::Int8Array::from(truncated.to_typed_array()).to_vec(),
+        vec![2, -2, 3, 127, 0]
+    );
+    let rounded = a.astype(
+        "int8",
+        Some(String::from("round")),
+        Some(String::from("wrap")),
+    );
+    assert_eq!(
+        js_sys::Int8Array::from(rounded.unwrap().to_typed_array()).to_vec(),
+        vec![2, -2, 4, 44, 0]
+    );
+    let b = Ndarray::new(JsValue::from(js_sys::Int32Array::from(
+        &[300, -1, 70000][..],
+    )));
+    assert_eq!(
+        js_sys::Uint16Array::from(b.astype("uint16", None, None).unwrap().to_typed_array())
+            .to_vec(),
+        vec![300, 65535, 4464]
+    );
+    let saturated = b.astype("uint16", None, Some(String::from("saturate")));
+    assert_eq!(
+        js_sys::Uint16Array::from(saturated.unwrap().to_typed_array()).to_vec(),
+        vec![300, 0, 65535]
+    );
+    assert_eq!(b.astype("bool", None, None).unwrap().dtype(), "bool");
+    assert!(b.astype("float128", None, None).is_err());
+    assert!(b
+        .astype("int8", Some(String::from("nearest")), None)
+        .is_err());
+    assert_eq!(
+        Ndarray::promote_types("int32", "float64").unwrap(),
+        "float64"
+    );
+    assert_eq!(Ndarray::promote_types("uint8", "int8").unwrap(), "int16");
+    assert_eq!(
+        Ndarray::promote_types("float16", "int16").unwrap(),
+        "float32"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_creation() {
+    let shape = js_interop::vec_isize_into_array(vec![2, 3]);
+    let zeros = Ndarray::zeros(&shape, None).unwrap();
+    assert_eq!(zeros.dtype(), "float64");
+    assert_eq!(zeros.shape(), &vec![2, 3]);
+    assert_eq!(
+        zeros.sum(JsValue::undefined(), None).unwrap().as_f64(),
+        Some(0.0)
+    );
+    let ones = Ndarray::ones(&shape, Some(String::from("int32"))).unwrap();
+    assert_eq!(ones.dtype(), "int32");
+    assert_eq!(
+        ones.sum(JsValue::undefined(), None).unwrap().as_f64(),
+        Some(6.0)
+    );
+    let full = Ndarray::full(&shape, 2.5, Some(String::from("float32"))).unwrap();
+    assert_eq!(
+        js_sys::Float32Array::from(full.to_typed_array()).to_vec(),
+        vec![2.5; 6]
+    );
+    let eye = Ndarray::eye(2.0, Some(3.0), Some(1), None).unwrap();
+    assert_eq!(
+        js_sys::Float64Array::from(eye.to_typed_array()).to_vec(),
+        vec![0.0, 1.0, 0.0, 0.0, 0.0, 1.0]
+    );
+    let identity = Ndarray::identity(3.0, Some(String::from("uint8"))).unwrap();
+    assert_eq!(
+        js_sys::Uint8Array::from(identity.to_typed_array()).to_vec(),
+        vec![1, 0, 0, 0, 1, 0, 0, 0, 1]
+    );
+    let like = Ndarray::full_like(&ones, 7.0, None).unwrap();
+    assert_eq!(like.dtype(), "int32");
+    assert_eq!(like.shape(), &vec![2, 3]);
+    assert_eq!(
+        Ndarray::zeros_like(&ones, Some(String::from("bool")))
+            .unwrap()
+            .dtype(),
+        "bool"
+    );
+    assert!(Ndarray::zeros(&shape, Some(String::from("float8"))).is_err());
+    // negative, fractional and too large dimensions are rejected
+    for dims in [vec![2.0, -3.0], vec![2.5], vec![f64::NAN], vec![1e300]] {
+        let shape = dims
+            .into_iter()
+            .map(JsValue::from)
+            .collect::<js_sys::Array>();
+        assert!(Ndarray::zeros(&shape, None).is_err());
+    }
+    let huge = (usize::MAX as f64).sqrt().ceil() + 1.0;
+    let shape = vec![huge, huge]
+        .into_iter()
+        .map(JsValue::from)
+        .collect::<js_sys::Array>();
+    assert!(Ndarray::zeros(&shape, None).is_err());
+    assert!(Ndarray::eye(-3.0, None, None, None).is_err());
+    assert!(Ndarray::eye(2.0, Some(1.5), None, None).is_err());
+    assert!(Ndarray::eye(huge, Some(huge), None, None).is_err());
+    assert!(Ndarray::identity(-1.0, None).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_ranges() {
+    let range = Ndarray::arange(5.0, Some(0.0), Some(-2.0), None).unwrap();
+    assert_eq!(range.dtype(), "int32");
+    assert_eq!(
+        js_sys::Int32Array::from(range.to_typed_array()).to_vec(),
+        vec![5, 3, 1]
+    );
+    assert_eq!(
+        Ndarray::arange(1.0, Some(2.0), Some(0.5), None)
+            .unwrap()
+            .dtype(),
+        "float64"
+    );
+    assert!(Ndarray::arange(0.0, Some(1.0), Some(0.0), None).is_err());
+    let x = Ndarray::linspace(0.0, 1.0, Some(5.0), None, None).unwrap();
+    assert_eq!(
+        js_sys::Float64Array::from(x.to_typed_array()).to_vec(),
+        vec![0.0, 0.25, 0.5, 0.75, 1.0]
+    );
+    let y = Ndarray::logspace(0.0, 2.0, Some(3.0), None, None, None).unwrap();
+    assert_eq!(
+        js_sys::Float64Array::from(y.to_typed_array()).to_vec(),
+        vec![1.0, 10.0, 100.0]
+    );
+    let z = Ndarray::geomspace(-1.0, -1000.0, Some(4.0), None, None).unwrap();
+    assert_eq!(
+        js_sys::Float64Array::from(z.to_typed_array()).to_vec(),
+        vec![-1.0, -10.0, -100.0, -1000.0]
+    );
+    assert!(Ndarray::geomspace(1.0, -1.0, None, None, None).is_err());
+    assert!(Ndarray::linspace(0.0, 1.0, Some(-1.0), None, None).is_err());
+    assert!(Ndarray::logspace(0.0, 1.0, Some(2.5), None, None, None).is_err());
+    assert!(Ndarray::geomspace(1.0, 2.0, Some(f64::NAN), None, None).is_err());
+    assert!(Ndarray::arange(0.0, Some(f64::INFINITY), None, None).is_err());
+    assert!(Ndarray::arange(f64::NAN, None, None, None).is_err());
+    assert!(Ndarray::arange(0.0, Some(1e300), Some(1e-300), None).is_err());
+    let inputs = js_sys::Array::of2(&JsValue::from(x), &JsValue::from(y));
+    let grids = Ndarray::meshgrid(&inputs, None).unwrap();
+    let xx = Ndarray::try_from_js_value(grids.get(0)).unwrap();
+    let yy = Ndarray::try_from_js_value(grids.get(1)).unwrap();
+    assert_eq!(xx.shape(), &vec![3, 5]);
+    assert_eq!(
+        yy.get(js_interop::vec_isize_into_array(vec![2, 4]))
+            .unwrap()
+            .as_f64(),
+        Some(100.0)
+    );
+    // the inputs can still be used after creating the grids
+    let x = Ndarray::try_from_js_value(inputs.get(0)).unwrap();
+    let grids = Ndarray::meshgrid(
+        &js_sys::Array::of2(&JsValue::from(x), &inputs.get(1)),
+        Some(String::from("ij")),
+    )
+    .unwrap();
+    let xx = Ndarray::try_from_js_value(grids.get(0)).unwrap();
+    assert_eq!(xx.shape(), &vec![5, 3]);
+    assert_eq!(
+        xx.get(js_interop::vec_isize_into_array(vec![4, 0]))
+            .unwrap()
+            .as_f64(),
+        Some(1.0)
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_transpose() {
+    let mut ndarray = Ndarray::arange(24.0, None, None, Some(String::from("float64"))).unwrap();
+    ndarray
+        .reshape(&js_interop::vec_isize_into_array(vec![2, 3, 4]))
+        .unwrap();
+    let transposed = ndarray.transposed();
+    assert_eq!(transposed.shape(), &vec![4, 3, 2]);
+    assert_eq!(
+        transposed
+            .get(js_interop::vec_isize_into_array(vec![3, 1, 0]))
+            .unwrap()
+            .as_f64(),
+        Some(7.0)
+    );
+    let permuted = ndarray
+        .transpose(Some(js_interop::vec_isize_into_array(vec![1, -1, 0])))
+        .unwrap();
+    assert_eq!(permuted.shape(), &vec![3, 4, 2]);
+    assert_eq!(
+        js_sys::Float64Array::from(permuted.to_typed_array()).to_vec()[..4],
+        [0.0, 12.0, 1.0, 13.0]
+    );
+    assert!(ndarray
+        .permute_axes(&js_interop::vec_isize_into_array(vec![0, 0, 1]))
+        .is_err());
+    assert!(ndarray
+        .transpose(Some(js_interop::vec_isize_into_array(vec![1, 0])))
+        .is_err());
+    assert_eq!(ndarray.swap_axes(0, -1).unwrap().shape(), &vec![4, 3, 2]);
+    let moved = ndarray.move_axis(0, -1).unwrap();
+    assert_eq!(moved.shape(), &vec![3, 4, 2]);
+    assert_eq!(
+        moved
+            .get(js_interop::vec_isize_into_array(vec![2, 1, 1]))
+            .unwrap()
+            .as_f64(),
+        Some(21.0)
+    );
+    assert!(ndarray.move_axis(3, 0).is_err());
+
+    // the offsets of a slice are permuted with its axes
+    let slice = ndarray
+        .slice(js_interop::test::vecvec_isize_into_arrayarray(vec![
+            vec![1, 2],
+            vec![1, 3],
+            vec![0, 4],
+        ]))
+        .unwrap();
+    let transposed = slice.transposed();
+    assert_eq!(transposed.shape(), &vec![4, 2, 1]);
+    assert_eq!(
+        js_sys::Float64Array::from(transposed.to_typed_array()).to_vec(),
+        vec![16.0, 20.0, 17.0, 21.0, 18.0, 22.0, 19.0, 23.0]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_flip_rot90() {
+    let mut ndarray = Ndarray::arange(12.0, None, None, None).unwrap();
+    ndarray
+        .reshape(&js_interop::vec_isize_into_array(vec![3, 4]))
+        .unwrap();
+    let values = |x: &Ndarray| js_sys::Int32Array::from(x.to_typed_array()).to_vec();
+    assert_eq!(
+        values(&ndarray.flipud().unwrap()),
+        vec![8, 9, 10, 11, 4, 5, 6, 7, 0, 1, 2, 3]
+    );
+    assert_eq!(
+        values(&ndarray.fliplr().unwrap()),
+        vec![3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8]
+    );
+    let flipped = ndarray.flip(JsValue::UNDEFINED).unwrap();
+    assert_eq!(flipped.strides(), &vec![-4, -1]);
+    assert_eq!(values(&flipped), (0..12).rev().collect::<Vec<i32>>());
+    assert_eq!(
+        values(&flipped.flip(JsValue::from(-1)).unwrap()),
+        vec![8, 9, 10, 11, 4, 5, 6, 7, 0, 1, 2, 3]
+    );
+    assert!(ndarray.flip(JsValue::from(2)).is_err());
+
+    let rotated = ndarray.rot90(None, None).unwrap();
+    assert_eq!(rotated.shape(), &vec![4, 3]);
+    assert_eq!(values(&rotated), vec![3, 7, 11, 2, 6, 10, 1, 5, 9, 0, 4, 8]);
+    assert_eq!(
+        values(&ndarray.rot90(Some(-1), None).unwrap()),
+        vec![8, 4, 0, 9, 5, 1, 10, 6, 2, 11, 7, 3]
+    );
+    assert_eq!(
+        values(&ndarray.rot90(Some(2), None).unwrap()),
+        (0..12).rev().collect::<Vec<i32>>()
+    );
+    assert!(ndarray
+        .rot90(None, Some(js_interop::vec_isize_into_array(vec![1, -1])))
+        .is_err());
+
+    // negative steps walk backwards through the data
+    let reversed = ndarray
+        .slice(js_sys::Array::of2(
+            &range(&[None, None, Some(-2)]),
+            &range(&[None, None, Some(-1)]),
+        ))
+        .unwrap();
+    assert_eq!(reversed.shape(), &vec![2, 4]);
+    assert_eq!(values(&reversed), vec![11, 10, 9, 8, 3, 2, 1, 0]);
+    let strided = ndarray
+        .slice(js_interop::test::vecvec_isize_into_arrayarray(vec![
+            vec![1, 3],
+            vec![1, 4, 2],
+        ]))
+        .unwrap();
+    assert_eq!(values(&strided), vec![5, 7, 9, 11]);
+    assert_eq!(values(&strided.fliplr().unwrap()), vec![7, 5, 11, 9]);
+}
+
+#[wasm_bindgen_test]
+fn test_slice_ranges() {
+    let ndarray = Ndarray::arange(10.0, None, None, None).unwrap();
+    let values = |definition: &[Option<isize>]| {
+        let slice = ndarray
+            .slice(js_sys::Array::of1(&range(definition)))
+            .unwrap();
+        js_sys::Int32Array::from(slice.to_typed_array()).to_vec()
+    };
+    assert_eq!(values(&[Some(2), Some(5)]), vec![2, 3, 4]);
+    assert_eq!(values(&[Some(1), Some(8), Some(3)]), vec![1, 4, 7]);
+    assert_eq!(values(&[Some(1), Some(9), Some(3)]), vec![1, 4, 7]);
+    assert_eq!(values(&[Some(-3), None]), vec![7, 8, 9]);
+    assert_eq!(values(&[None, Some(-7)]), vec![0, 1, 2]);
+    assert_eq!(values(&[None, None, Some(4)]), vec![0, 4, 8]);
+    assert_eq!(values(&[Some(8), Some(2), Some(-3)]), vec![8, 5]);
+    assert_eq!(values(&[None, Some(6), Some(-2)]), vec![9, 7]);
+    assert_eq!(values(&[Some(5), Some(2)]), Vec::<i32>::new());
+    assert_eq!(values(&[Some(10), None]), Vec::<i32>::new());
+    assert_eq!(values(&[]), (0..10).collect::<Vec<i32>>());
+    assert_eq!(values(&[None, None, Some(isize::MAX)]), vec![0]);
+    assert_eq!(values(&[None, None, Some(isize::MIN)]), vec![9]);
+    assert_eq!(values(&[Some(3), None, Some(isize::MIN)]), vec![3]);
+    let huge = js_sys::Array::of3(&JsValue::NULL, &JsValue::NULL, &JsValue::from(1e30));
+    let slice = ndarray.slice(js_sys::Array::of1(&huge)).unwrap();
+    assert_eq!(
+        js_sys::Int32Array::from(slice.to_typed_array()).to_vec(),
+        vec![0]
+    );
+
+    let slice =
+        |definition: &[Option<isize>]| ndarray.slice(js_sys::Array::of1(&range(definition)));
+    assert!(slice(&[Some(11), None]).is_err());
+    assert!(slice(&[None, Some(-11)]).is_err());
+    assert!(slice(&[None, None, Some(0)]).is_err());
+    assert!(slice(&[Some(0), Some(1), Some(1), Some(1)]).is_err());
+    assert!(ndarray
+        .slice(js_sys::Array::of2(&JsValue::NULL, &JsValue::NULL))
+        .is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_index() {
+    let mut ndarray = Ndarray::arange(120.0, None, None, None).unwrap();
+    ndarray
+        .reshape(&js_interop::vec_isize_into_array(vec![4, 5, 6]))
+        .unwrap();
+    let index = |input: &str| Ndarray::try_from_js_value(ndarray.index(input).unwrap()).unwrap();
+    let values = |view: &Ndarray| js_sys::Int32Array::from(view.to_typed_array()).to_vec();
+
+    let view = index("1:3, ::2, ..., None, -1");
+    assert_eq!(view.shape(), &vec![2, 3, 1]);
+    assert_eq!(values(&view), vec![35, 47, 59, 65, 77, 89]);
+    let view = index("-1, ::-2");
+    assert_eq!(view.shape(), &vec![3, 6]);
+    assert_eq!(values(&view)[..6], [114, 115, 116, 117, 118, 119]);
+    let view = index("..., 0");
+    assert_eq!(view.shape(), &vec![4, 5]);
+    assert_eq!(values(&view)[..3], [0, 6, 12]);
+    let view = index("None, 2, newaxis");
+    assert_eq!(view.shape(), &vec![1, 1, 5, 6]);
+    assert_eq!(values(&view)[0], 60);
+    assert_eq!(index("").shape(), &vec![4, 5, 6]);
+    assert_eq!(index("0,").shape(), &vec![5, 6]);
+    assert_eq!(ndarray.index("1, 2, 3").unwrap().as_f64().unwrap(), 45.0);
+
+    assert!(ndarray.index("4").is_err());
+    assert!(ndarray.index("0, 0, 0, 0").is_err());
+    assert!(ndarray.index("..., 0, ...").is_err());
+    assert!(ndarray.index("1:2:3:4").is_err());
+    assert!(ndarray.index("::0").is_err());
+    assert!(ndarray.index("a").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_compose_views() {
+    let values = |x: &Ndarray| js_sys::Int32Array::from(x.to_typed_array()).to_vec();
+    // materializes a view, so the following step starts from a contiguous Ndarray
+    let copy = |x: &Ndarray| {
+        let mut copy = Ndarray::new(JsValue::from(x.to_typed_array()));
+        let shape = x.shape().iter().map(|&x| x as isize).collect();
+        copy.reshape(&js_interop::vec_isize_into_array(shape))
+            .unwrap();
+        copy
+    };
+    let mut ndarray = Ndarray::arange(60.0, None, None, None).unwrap();
+    ndarray
+        .reshape(&js_interop::vec_isize_into_array(vec![3, 4, 5]))
+        .unwrap();
+    let slice = ndarray
+        .slice(js_sys::Array::of3(
+            &range(&[Some(1), None]),
+            &range(&[None, None, Some(-1)]),
+            &range(&[Some(0), Some(5), Some(2)]),
+        ))
+        .unwrap();
+    let slices = ndarray
+        .slices(js_interop::test::vecvec_isize_into_arrayarray(vec![
+            vec![2, 0],
+            vec![1, 3, 0],
+            vec![4, 1],
+        ]))
+        .unwrap();
+    assert!(matches!(ndarray.format(), Format::None));
+    assert!(matches!(slice.format(), Format::Slice(_)));
+    assert!(matches!(slices.format(), Format::Slices(_)));
+
+    let steps: Vec<Box<dyn Fn(&Ndarray) -> Ndarray>> = vec![
+        Box::new(|x| {
+            x.slice(js_sys::Array::of2(
+                &range(&[Some(1), None]),
+                &range(&[None, None, Some(-1)]),
+            ))
+            .unwrap()
+        }),
+        Box::new(|x| {
+            let shape = x.shape();
+            x.slices(js_interop::test::vecvec_isize_into_arrayarray(vec![
+                (0..shape[0] as isize).rev().collect(),
+                vec![shape[1] as isize - 1, 0],
+            ]))
+            .unwrap()
+        }),
+        Box::new(|x| x.transpose(None).unwrap()),
+        Box::new(|x| {
+            let mut shape = x.shape().iter().map(|&x| x as isize).collect::<Vec<_>>();
+            shape.swap(0, 1);
+            let mut x = x.view();
+            x.reshape(&js_interop::vec_isize_into_array(shape)).unwrap();
+            x
+        }),
+    ];
+    for start in [&ndarray, &slice, &slices].iter() {
+        for first in steps.iter() {
+            for second in steps.iter() {
+                let composed = second(&first(start));
+                let expected = second(&copy(&first(&copy(start))));
+                assert_eq!(composed.shape(), expected.shape());
+                assert_eq!(values(&composed), values(&expected));
+            }
+        }
+    }
+
+    // slicing a view selects from the elements of the view
+    let view = slices
+        .slices(js_interop::test::vecvec_isize_into_arrayarray(vec![vec![
+            1,
+        ]]))
+        .unwrap();
+    assert_eq!(values(&view), vec![9, 6, 19, 16, 4, 1]);
+    let view = slice.slice(js_sys::Array::of1(&range(&[Some(1)]))).unwrap();
+    assert_eq!(values(&view)[..3], [55, 57, 59]);
+    assert!(slices
+        .slices(js_interop::test::vecvec_isize_into_arrayarray(vec![vec![
+            2
+        ]]))
+        .is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_broadcast_to() {
+    let input = (1..4).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
+    let ndarray = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
+    let broadcast = ndarray
+        .broadcast_to(&js_interop::vec_isize_into_array(vec![2, 3]))
+        .unwrap();
+    assert_eq!(broadcast.strides(), &vec![0, 1]);
+    assert_eq!(
+        broadcast
+            .get(js_interop::vec_isize_into_array(vec![1, 2]))
+            .unwrap()
+            .as_f64()
+            .unwrap(),
+        3.0
+    );
+    assert!(ndarray
+        .broadcast_to(&js_interop::vec_isize_into_array(vec![2, 2]))
+        .is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_broadcast_shapes() {
+    let shape = Ndarray::broadcast_shapes(&js_interop::test::vecvec_isize_into_arrayarray(vec![
+        vec![2, 1],
+        vec![3],
+        vec![4, 1, 1],
+    ]))
+    .unwrap();
+    assert_eq!(js_interop::into_vec_usize(&shape).unwrap(), vec![4, 2, 3]);
+    assert!(
+        Ndarray::broadcast_shapes(&js_interop::test::vecvec_isize_into_arrayarray(vec![
+            vec![2],
+            vec![3],
+        ]))
+        .is_err()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_sum_axis() {
+    let input = (1..7).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
+    let mut ndarray = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
+    ndarray
+        .reshape(&js_interop::vec_isize_into_array(vec![2, 3]))
+        .unwrap();
+    assert_eq!(
+        ndarray
+            .sum(JsValue::undefined(), None)
+            .unwrap()
+            .as_f64()
+            .unwrap(),
+        21.0
+    );
+    let columns = ndarray.sum(JsValue::from_f64(0.0), None).unwrap();
+    assert!(columns.as_f64().is_none());
+    let rows = ndarray.mean(JsValue::from_f64(-1.0), Some(true)).unwrap();
+    assert!(rows.as_f64().is_none());
+}
+
+#[wasm_bindgen_test]
+fn test_min_max_slice() {
+    let input = (1..28).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
+    let mut ndarray = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
+    let shape = js_interop::vec_isize_into_array(vec![3, 3, 3]);
+    ndarray.reshape(&shape).unwrap();
+    let slice = ndarray
+        .slice(js_interop::test::vecvec_isize_into_arrayarray(vec![
+            vec![1, 3],
+            vec![0, 3],
+            vec![0, 2],
+        ]))
+        .unwrap();
+    assert_eq!(
+        slice
+            .min(JsValue::undefined(), None)
+            .unwrap()
+            .as_f64()
+            .unwrap(),
+        10.0
+    );
+    assert_eq!(
+        slice
+            .max(JsValue::undefined(), None)
+            .unwrap()
+            .as_f64()
+            .unwrap(),
+        26.0
+    );
+    assert_eq!(
+        slice
+            .argmax(JsValue::undefined(), None)
+            .unwrap()
+            .as_f64()
+            .unwrap(),
+        11.0
+    );
+    assert!(slice
+        .argmax(
+            JsValue::from(js_interop::vec_isize_into_array(vec![0, 1])),
+            None
+        )
+        .is_err());
+}
+
+/// Creates a float64 Ndarray of the given shape with small integer values, so that products are exact.
+fn matrix(shape: Vec<isize>) -> Ndarray {
+    let size = shape.iter().product::<isize>() as f64;
+    let mut ndarray = Ndarray::arange(size, None, None, Some(String::from("float64")))
+        .unwrap()
+        .rem(&JsValue::from(7.0))
+        .unwrap()
+        .sub(&JsValue::from(3.0))
+        .unwrap();
+    ndarray
+        .reshape(&js_interop::vec_isize_into_array(shape))
+        .unwrap();
+    ndarray
+}
+
+/// Multiplies the row-major matrices `a` (m x k) and `b` (k x n) with a naive triple loop.
+fn naive_matmul(a: &[f64], b: &[f64], m: usize, k: usize, n: usize) -> Vec<f64> {
+    let mut c = vec![0.0; m * n];
+    for i in 0..m {
+        for j in 0..n {
+            for p in 0..k {
+                c[i * n + j] += a[i * k + p] * b[p * n + j];
+            }
+        }
+    }
+    c
+}
+
+#[wasm_bindgen_test]
+fn test_matmul() {
+    let values = |x: &Ndarray| js_sys::Float64Array::from(x.to_typed_array()).to_vec();
+    let product =
+        |a: &Ndarray, b: &Ndarray| Ndarray::try_from_js_value(a.matmul(b).unwrap()).unwrap();
+    let input = (1..7).into_iter().map(|x| x as f64).collect::<Vec<f64>>();
+    let mut a = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        input,
+    )));
+    a.reshape(&js_interop::vec_isize_into_array(vec![2, 3]))
+        .unwrap();
+    let x = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        vec![1.0, 0.0, -1.0],
+    )));
+    let ax = product(&a, &x);
+    assert_eq!(ax.shape(), &vec![2]);
+    assert_eq!(values(&ax), vec![-2.0, -2.0]);
+    assert_eq!(x.dot(&x).unwrap().as_f64().unwrap(), 2.0);
+    assert_eq!(a.vdot(&a).unwrap().as_f64().unwrap(), 91.0);
+    assert!(a.matmul(&a).is_err());
+
+    // sizes that aren't multiples of the block size
+    let a = matrix(vec![37, 45]);
+    let b = matrix(vec![45, 33]);
+    let c = product(&a, &b);
+    assert_eq!(c.shape(), &vec![37, 33]);
+    assert_eq!(
+        values(&c),
+        naive_matmul(&values(&a), &values(&b), 37, 45, 33)
+    );
+
+    // transposed and sliced operands
+    let at = matrix(vec![45, 37]).transposed();
+    let bs = matrix(vec![50, 66])
+        .slice(js_sys::Array::of2(
+            &range(&[Some(5), None]),
+            &range(&[None, None, Some(2)]),
+        ))
+        .unwrap();
+    assert_eq!(bs.shape(), &vec![45, 33]);
+    let c = product(&at, &bs);
+    assert_eq!(
+        values(&c),
+        naive_matmul(&values(&at), &values(&bs), 37, 45, 33)
+    );
+
+    // batch of shape [2, 1] broadcast against [3]
+    let a = matrix(vec![2, 1, 4, 5]);
+    let b = matrix(vec![3, 5, 6]);
+    let c = product(&a, &b);
+    assert_eq!(c.shape(), &vec![2, 3, 4, 6]);
+    let (a, b, c) = (values(&a), values(&b), values(&c));
+    for i in 0..2 {
+        for j in 0..3 {
+            let expected =
+                naive_matmul(&a[i * 20..(i + 1) * 20], &b[j * 30..(j + 1) * 30], 4, 5, 6);
+            assert_eq!(c[(i * 3 + j) * 24..(i * 3 + j + 1) * 24], expected[..]);
+        }
+    }
+
+    // inner sums over the last axes, outer multiplies all pairs
+    let a = matrix(vec![2, 3]);
+    let b = matrix(vec![4, 3]);
+    let inner = Ndarray::try_from_js_value(a.inner(&b).unwrap()).unwrap();
+    assert_eq!(inner.shape(), &vec![2, 4]);
+    assert_eq!(
+        values(&inner),
+        naive_matmul(&values(&a), &values(&b.transposed()), 2, 3, 4)
+    );
+    let outer = Ndarray::try_from_js_value(x.outer(&b).unwrap()).unwrap();
+    assert_eq!(outer.shape(), &vec![3, 12]);
+    assert_eq!(
+        values(&outer),
+        naive_matmul(&values(&x), &values(&b), 3, 1, 12)
+    );
+}
@@ -0,0 +1,529 @@
+use wasm_bindgen::prelude::*;
+
+use crate::js_interop;
+use crate::ndarray::*;
+
+pub mod test;
+
+/// Pseudo-random number generator based on xoshiro256**.
+///
+/// A Generator created with a seed always produces the same sequence of numbers, independent of the platform. The samplers fill Ndarrays of a given shape in row-major order.
+#[wasm_bindgen]
+pub struct Generator {
+    state: [u64; 4],
+}
+
+impl Generator {
+    /// Creates a Generator whose state is derived from `seed` with SplitMix64, so that similar seeds lead to unrelated sequences.
+    pub fn from_seed(seed: u64) -> Generator {
+        let mut x = seed;
+        let mut state = [0; 4];
+        for word in state.iter_mut() {
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            *word = z ^ (z >> 31);
+        }
+        Generator { state }
+    }
+
+    /// Returns the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+        result
+    }
+
+    /// Returns a uniformly distributed number in the interval `[0, 1)` with 53 random bits.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a uniformly distributed integer in the range `[0, n)` without modulo bias (Lemire's method). `n` must not be zero.
+    pub fn next_below(&mut self, n: u64) -> u64 {
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let product = self.next_u64() as u128 * n as u128;
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// Samples the standard normal distribution with the polar method.
+    pub fn standard_normal(&mut self) -> f64 {
+        loop {
+            let u = 2.0 * self.next_f64() - 1.0;
+            let v = 2.0 * self.next_f64() - 1.0;
+            let s = u * u + v * v;
+            if s > 0.0 && s < 1.0 {
+                return u * (-2.0 * s.ln() / s).sqrt();
+            }
+        }
+    }
+
+    /// Samples the exponential distribution with rate one by inversion.
+    pub fn standard_exponential(&mut self) -> f64 {
+        -(1.0 - self.next_f64()).ln()
+    }
+
+    /// Samples the gamma distribution with shape `alpha` and scale one (Marsaglia and Tsang). `alpha` must be positive.
+    pub fn standard_gamma(&mut self, alpha: f64) -> f64 {
+        if alpha < 1.0 {
+            // a sample for alpha + 1 is boosted down with a uniform power
+            let u = 1.0 - self.next_f64();
+            return self.standard_gamma(alpha + 1.0) * u.powf(1.0 / alpha);
+        }
+        let d = alpha - 1.0 / 3.0;
+        let c = 1.0 / (9.0 * d).sqrt();
+        loop {
+            let x = self.standard_normal();
+            let v = 1.0 + c * x;
+            if v <= 0.0 {
+                continue;
+            }
+            let v = v * v * v;
+            let u = self.next_f64();
+            if u < 1.0 - 0.0331 * x.powi(4) || u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+                return d * v;
+            }
+        }
+    }
+
+    /// Samples the beta distribution with the positive parameters `a` and `b`.
+    ///
+    /// Parameters below one use Jöhnk's algorithm, which stays accurate where the ratio of gamma samples would underflow.
+    pub fn standard_beta(&mut self, a: f64, b: f64) -> f64 {
+        if a < 1.0 && b < 1.0 {
+            loop {
+                let u = 1.0 - self.next_f64();
+                let v = 1.0 - self.next_f64();
+                let x = u.powf(1.0 / a);
+                let y = v.powf(1.0 / b);
+                if x + y <= 1.0 {
+                    if x + y > 0.0 {
+                        return x / (x + y);
+                    }
+                    let log_x = u.ln() / a;
+                    let log_y = v.ln() / b;
+                    let log_max = log_x.max(log_y);
+                    let (log_x, log_y) = (log_x - log_max, log_y - log_max);
+                    return (log_x - (log_x.exp() + log_y.exp()).ln()).exp();
+                }
+            }
+        }
+        let x = self.standard_gamma(a);
+        let y = self.standard_gamma(b);
+        x / (x + y)
+    }
+
+    /// Samples the binomial distribution with `n` trials and success probability `p`.
+    ///
+    /// Large `n` are reduced with order statistics of uniform samples, which are beta distributed, until few enough trials are left to count successes directly (Knuth, TAOCP 3.4.1).
+    pub fn binomial_sample(&mut self, n: u64, p: f64) -> u64 {
+        let (mut n, mut p, mut k) = (n, p, 0);
+        while n > 64 {
+            let a = 1 + n / 2;
+            let b = n + 1 - a;
+            let x = self.standard_beta(a as f64, b as f64);
+            if x >= p {
+                n = a - 1;
+                p /= x;
+            } else {
+                k += a;
+                n = b - 1;
+                p = (p - x) / (1.0 - x);
+            }
+        }
+        k + (0..n).filter(|_| self.next_f64() < p).count() as u64
+    }
+
+    /// Samples the Poisson distribution with mean `lam`.
+    ///
+    /// Large means are reduced with gamma distributed arrival times until the product of uniform samples can be used (Knuth, TAOCP 3.4.1).
+    pub fn poisson_sample(&mut self, lam: f64) -> u64 {
+        let (mut lam, mut k) = (lam, 0);
+        while lam > 16.0 {
+            let m = (lam * 0.875).floor() as u64;
+            let x = self.standard_gamma(m as f64);
+            if x < lam {
+                k += m;
+                lam -= x;
+            } else {
+                return k + self.binomial_sample(m - 1, lam / x);
+            }
+        }
+        let limit = (-lam).exp();
+        let mut product = self.next_f64();
+        while product > limit {
+            k += 1;
+            product *= self.next_f64();
+        }
+        k
+    }
+
+    /// Returns `k` distinct integers drawn uniformly from `[0, n)` in random order. For `k == n` this is a random permutation.
+    pub fn sample_indices(&mut self, n: usize, k: usize) -> Vec<usize> {
+        // partial Fisher-Yates shuffle
+        let mut indices = (0..n).collect::<Vec<usize>>();
+        for i in 0..k {
+            let j = i + self.next_below((n - i) as u64) as usize;
+            indices.swap(i, j);
+        }
+        indices.truncate(k);
+        indices
+    }
+
+    /// Returns an index drawn with probabilities proportional to the non-negative `weights`, of which at least one has to be positive.
+    pub fn weighted_index(&mut self, weights: &[f64]) -> usize {
+        let total = weights.iter().sum::<f64>();
+        let u = self.next_f64() * total;
+        let mut sum = 0.0;
+        for (i, &weight) in weights.iter().enumerate() {
+            sum += weight;
+            if u < sum && weight > 0.0 {
+                return i;
+            }
+        }
+        // rounding errors can leave `u` above the sum, which picks the last possible index
+        weights.iter().rposition(|&weight| weight > 0.0).unwrap()
+    }
+
+    /// Creates a Ndarray of the given shape and type, defaulting to `default`, with elements drawn by `sample`.
+    fn sample<F>(
+        &mut self,
+        shape: &js_sys::Array,
+        dtype: Option<String>,
+        default: DType,
+        mut sample: F,
+    ) -> Result<Ndarray, JsValue>
+    where
+        F: FnMut(&mut Generator) -> f64,
+    {
+        let shape = js_interop::into_shape(shape)?;
+        let dtype = parse_dtype(dtype, default)?;
+        let data = (0..shape.iter().product::<usize>())
+            .map(|_| sample(self))
+            .collect();
+        Ndarray::from_f64_vec(data, shape, dtype).map_err(|err| JsValue::from_str(&err))
+    }
+}
+
+/// Returns an error with `message` unless `valid` holds.
+fn check(valid: bool, message: &str) -> Result<(), JsValue> {
+    if valid {
+        Ok(())
+    } else {
+        Err(JsValue::from_str(message))
+    }
+}
+
+#[wasm_bindgen]
+impl Generator {
+    /// Creates a Generator from a non-negative integer `seed`. Without a seed the Generator is seeded from `Math.random`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: Option<f64>) -> Result<Generator, JsValue> {
+        let seed = match seed {
+            Some(seed) => {
+                check(
+                    seed >= 0.0 && seed.fract() == 0.0 && seed < u64::MAX as f64,
+                    "Seed must be a non-negative integer.",
+                )?;
+                seed as u64
+            }
+            None => {
+                let high = (js_sys::Math::random() * (1u64 << 32) as f64) as u64;
+                let low = (js_sys::Math::random() * (1u64 << 32) as f64) as u64;
+                (high << 32) | low
+            }
+        };
+        Ok(Generator::from_seed(seed))
+    }
+
+    /// Creates a Ndarray of the given shape with numbers that are uniformly distributed in `[0, 1)`. The type defaults to `float64`.
+    pub fn random(
+        &mut self,
+        shape: &js_sys::Array,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        self.sample(shape, dtype, DType::F64, Generator::next_f64)
+    }
+
+    /// Creates a Ndarray of the given shape with numbers that are uniformly distributed in `[low, high)`. The interval defaults to `[0, 1)` and the type to `float64`.
+    pub fn uniform(
+        &mut self,
+        shape: &js_sys::Array,
+        low: Option<f64>,
+        high: Option<f64>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let low = low.unwrap_or(0.0);
+        let high = high.unwrap_or(1.0);
+        check(
+            low.is_finite() && high.is_finite(),
+            "Bounds must be finite numbers.",
+        )?;
+        self.sample(shape, dtype, DType::F64, |generator| {
+            low + (high - low) * generator.next_f64()
+        })
+    }
+
+    /// Creates a Ndarray of the given shape with normally distributed numbers. `mean` defaults to zero, the standard deviation `std` to one and the type to `float64`.
+    pub fn normal(
+        &mut self,
+        shape: &js_sys::Array,
+        mean: Option<f64>,
+        std: Option<f64>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let mean = mean.unwrap_or(0.0);
+        let std = std.unwrap_or(1.0);
+        check(
+            mean.is_finite() && std.is_finite() && std >= 0.0,
+            "Normal distribution requires a finite mean and a non-negative standard deviation.",
+        )?;
+        self.sample(shape, dtype, DType::F64, |generator| {
+            mean + std * generator.standard_normal()
+        })
+    }
+
+    /// Creates a Ndarray of the given shape with uniformly distributed integers from `low` (inclusive) to `high` (exclusive). If `high` isn't given, the integers range from zero to `low`.
+    ///
+    /// The integers are computed exactly, also beyond 2^53. The bounds must lie in the range of 64-bit integers, the range may contain at most 2^64 - 1 integers and it must fit into an integer `dtype`. The type defaults to `int32` if both bounds fit into an `i32`, to `uint64` for integers above the range of `i64` and to `int64` otherwise.
+    pub fn integers(
+        &mut self,
+        shape: &js_sys::Array,
+        low: f64,
+        high: Option<f64>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let (low, high) = match high {
+            Some(high) => (low, high),
+            None => (0.0, low),
+        };
+        check(
+            low.fract() == 0.0 && high.fract() == 0.0 && low < high,
+            "Integers requires integer bounds with low less than high.",
+        )?;
+        // the bounds of 64-bit integers, which also keep the conversion to `i128` exact
+        check(
+            low >= -(2f64.powi(63)) && high <= 2f64.powi(64),
+            "Integers requires bounds in the range of 64-bit integers.",
+        )?;
+        let fits = low >= i32::MIN as f64 && high - 1.0 <= i32::MAX as f64;
+        let (low, high) = (low as i128, high as i128);
+        let range = (high - low) as u64;
+        check(
+            high - low <= u64::MAX as i128,
+            "Integers requires a range of at most 2^64 - 1 integers.",
+        )?;
+        let shape = js_interop::into_shape(shape)?;
+        let default = if fits {
+            DType::I32
+        } else if high - 1 > i64::MAX as i128 {
+            DType::U64
+        } else {
+            DType::I64
+        };
+        let dtype = parse_dtype(dtype, default)?;
+        if let Some((min, max)) = dtype.integer_bounds() {
+            if low < min || high - 1 > max {
+                return Err(JsValue::from_str(&format!(
+                    "Integers from {} to {} don't fit into dtype {}.",
+                    low,
+                    high,
+                    dtype.name()
+                )));
+            }
+        }
+        let data = (0..shape.iter().product::<usize>())
+            .map(|_| low.wrapping_add(self.next_below(range) as i128))
+            .collect();
+        Ndarray::from_i128_vec(data, shape, dtype).map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Creates a Ndarray of the given shape with exponentially distributed numbers. `scale`, the inverse of the rate, defaults to one and the type to `float64`.
+    pub fn exponential(
+        &mut self,
+        shape: &js_sys::Array,
+        scale: Option<f64>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let scale = scale.unwrap_or(1.0);
+        check(
+            scale.is_finite() && scale >= 0.0,
+            "Exponential distribution requires a non-negative scale.",
+        )?;
+        self.sample(shape, dtype, DType::F64, |generator| {
+            scale * generator.standard_exponential()
+        })
+    }
+
+    /// Creates a Ndarray of the given shape with gamma distributed numbers of shape parameter `alpha`. `scale` defaults to one and the type to `float64`.
+    pub fn gamma(
+        &mut self,
+        shape: &js_sys::Array,
+        alpha: f64,
+        scale: Option<f64>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let scale = scale.unwrap_or(1.0);
+        check(
+            alpha.is_finite() && alpha > 0.0 && scale.is_finite() && scale >= 0.0,
+            "Gamma distribution requires a positive shape parameter and a non-negative scale.",
+        )?;
+        self.sample(shape, dtype, DType::F64, |generator| {
+            scale * generator.standard_gamma(alpha)
+        })
+    }
+
+    /// Creates a Ndarray of the given shape with beta distributed numbers of the parameters `a` and `b`. The type defaults to `float64`.
+    pub fn beta(
+        &mut self,
+        shape: &js_sys::Array,
+        a: f64,
+        b: f64,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        check(
+            a.is_finite() && a > 0.0 && b.is_finite() && b > 0.0,
+            "Beta distribution requires positive parameters.",
+        )?;
+        self.sample(shape, dtype, DType::F64, |generator| {
+            generator.standard_beta(a, b)
+        })
+    }
+
+    /// Creates a Ndarray of the given shape with the numbers of successes in `n` trials with success probability `p`. The type defaults to `int32`.
+    pub fn binomial(
+        &mut self,
+        shape: &js_sys::Array,
+        n: f64,
+        p: f64,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        check(
+            n >= 0.0 && n.fract() == 0.0 && (0.0..=1.0).contains(&p),
+            "Binomial distribution requires a non-negative integer n and a probability p between 0 and 1.",
+        )?;
+        self.sample(shape, dtype, DType::I32, |generator| {
+            generator.binomial_sample(n as u64, p) as f64
+        })
+    }
+
+    /// Creates a Ndarray of the given shape with Poisson distributed numbers of mean `lam`, which defaults to one. The type defaults to `int32`.
+    pub fn poisson(
+        &mut self,
+        shape: &js_sys::Array,
+        lam: Option<f64>,
+        dtype: Option<String>,
+    ) -> Result<Ndarray, JsValue> {
+        let lam = lam.unwrap_or(1.0);
+        check(
+            lam.is_finite() && lam >= 0.0,
+            "Poisson distribution requires a non-negative mean.",
+        )?;
+        self.sample(shape, dtype, DType::I32, |generator| {
+            generator.poisson_sample(lam) as f64
+        })
+    }
+
+    /// Returns a random permutation of the integers from zero to `n` (exclusive) as a one-dimensional Ndarray. `n` must be a non-negative integer and the type defaults to `int32`.
+    pub fn permutation(&mut self, n: f64, dtype: Option<String>) -> Result<Ndarray, JsValue> {
+        let n = js_interop::into_dim(n)?;
+        let dtype = parse_dtype(dtype, DType::I32)?;
+        let data = self
+            .sample_indices(n, n)
+            .into_iter()
+            .map(|x| x as f64)
+            .collect();
+        Ndarray::from_f64_vec(data, vec![n], dtype).map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Shuffles the subarrays along `axis` (default 0) of the Ndarray in place. All subarrays along the other axes are reordered in the same way, so rows stay intact when shuffling along the first axis.
+    ///
+    /// The Ndarray must be the only owner of its data, so it can't be shuffled while views of it exist.
+    pub fn shuffle(&mut self, array: &mut Ndarray, axis: Option<isize>) -> Result<(), JsValue> {
+        let axis = normalize_axis(axis.unwrap_or(0), array.shape().len())
+            .map_err(|err| JsValue::from_str(&err))?;
+        let permutation = self.sample_indices(array.shape()[axis], array.shape()[axis]);
+        array.permute_along(axis, &permutation)
+    }
+
+    /// Draws random samples from the subarrays along the first axis of `array` and returns them as a new Ndarray.
+    ///
+    /// `size` is the shape of the samples, which replaces the first axis in the result. Without `size` a single subarray, or a single element of a one-dimensional Ndarray, is returned. Samples are drawn with replacement unless `replace` is `false`. The optional one-dimensional Ndarray `p` contains the probability of each subarray, otherwise all are equally likely.
+    pub fn choice(
+        &mut self,
+        array: &Ndarray,
+        size: Option<js_sys::Array>,
+        replace: Option<bool>,
+        p: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let n = match array.shape().first() {
+            Some(&n) => n,
+            None => {
+                return Err(JsValue::from_str(
+                    "Choice requires a Ndarray with at least one dimension.",
+                ))
+            }
+        };
+        let shape = match size {
+            Some(size) => js_interop::into_shape(&size)?,
+            None => vec![],
+        };
+        let k = shape.iter().product::<usize>();
+        let weights = if p.is_undefined() || p.is_null() {
+            None
+        } else {
+            let p = Ndarray::borrow_js(&p)?;
+            check(
+                p.shape() == &vec![n],
+                "Probabilities must be a one-dimensional Ndarray with one entry per choice.",
+            )?;
+            let p = p.0.convert::<f64>().to_vec();
+            check(
+                p.iter().all(|&x| x.is_finite() && x >= 0.0)
+                    && (p.iter().sum::<f64>() - 1.0).abs() < 1e-8,
+                "Probabilities must be non-negative and sum to one.",
+            )?;
+            Some(p)
+        };
+        let rows = if replace.unwrap_or(true) {
+            check(n > 0 || k == 0, "Cannot choose from an empty Ndarray.")?;
+            match weights {
+                Some(weights) => (0..k).map(|_| self.weighted_index(&weights)).collect(),
+                None => (0..k).map(|_| self.next_below(n as u64) as usize).collect(),
+            }
+        } else {
+            check(
+                k <= n,
+                "Cannot take a larger sample than the Ndarray contains without replacement.",
+            )?;
+            match weights {
+                Some(mut weights) => {
+                    check(
+                        weights.iter().filter(|&&x| x > 0.0).count() >= k,
+                        "Fewer non-zero probabilities than samples.",
+                    )?;
+                    (0..k)
+                        .map(|_| {
+                            let i = self.weighted_index(&weights);
+                            weights[i] = 0.0;
+                            i
+                        })
+                        .collect()
+                }
+                None => self.sample_indices(n, k),
+            }
+        };
+        Ok(array.take_rows(&rows, &shape).into_js_value())
+    }
+}
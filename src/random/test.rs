@@ -0,0 +1,276 @@
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen::convert::TryFromJsValue;
+use wasm_bindgen_test::*;
+
+use crate::js_interop;
+use crate::random::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn shape(input: Vec<isize>) -> js_sys::Array {
+    js_interop::vec_isize_into_array(input)
+}
+
+fn values(ndarray: &Ndarray) -> Vec<f64> {
+    js_sys::Float64Array::from(
+        ndarray
+            .astype("float64", None, None)
+            .unwrap()
+            .to_typed_array(),
+    )
+    .to_vec()
+}
+
+fn to_ndarray(value: JsValue) -> Ndarray {
+    Ndarray::try_from_js_value(value).unwrap()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[wasm_bindgen_test]
+fn test_seed() {
+    let a = Generator::new(Some(42.0))
+        .unwrap()
+        .normal(&shape(vec![10]), None, None, None)
+        .unwrap();
+    let b = Generator::new(Some(42.0))
+        .unwrap()
+        .normal(&shape(vec![10]), None, None, None)
+        .unwrap();
+    let c = Generator::new(Some(43.0))
+        .unwrap()
+        .normal(&shape(vec![10]), None, None, None)
+        .unwrap();
+    assert_eq!(values(&a), values(&b));
+    assert_ne!(values(&a), values(&c));
+    assert!(Generator::new(Some(-1.0)).is_err());
+    assert!(Generator::new(Some(0.5)).is_err());
+    assert!(Generator::new(None).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn test_uniform_integers() {
+    let mut generator = Generator::new(Some(1.0)).unwrap();
+    let x = generator
+        .uniform(&shape(vec![100, 10]), Some(-2.0), Some(3.0), None)
+        .unwrap();
+    assert_eq!(x.shape(), &vec![100, 10]);
+    assert_eq!(x.dtype(), "float64");
+    assert!(values(&x).iter().all(|&x| (-2.0..3.0).contains(&x)));
+    let x = generator
+        .random(&shape(vec![5]), Some(String::from("float32")))
+        .unwrap();
+    assert_eq!(x.dtype(), "float32");
+
+    let x = generator
+        .integers(&shape(vec![1000]), 5.0, None, None)
+        .unwrap();
+    assert_eq!(x.dtype(), "int32");
+    let x = values(&x);
+    assert!(x
+        .iter()
+        .all(|&x| x.fract() == 0.0 && (0.0..5.0).contains(&x)));
+    assert!((0..5).all(|i| x.contains(&(i as f64))));
+    let x = generator
+        .integers(
+            &shape(vec![10]),
+            -3.0,
+            Some(-1.0),
+            Some(String::from("int8")),
+        )
+        .unwrap();
+    assert_eq!(x.dtype(), "int8");
+    assert!(values(&x).iter().all(|&x| x == -3.0 || x == -2.0));
+    assert!(generator
+        .integers(&shape(vec![1]), 2.0, Some(2.0), None)
+        .is_err());
+
+    // bounds above 2^53 are exact in floating point only in steps of 256, the integers in between are drawn too
+    let low = 1i64 << 60;
+    let x = generator
+        .integers(
+            &shape(vec![100]),
+            low as f64,
+            Some((low + 512) as f64),
+            None,
+        )
+        .unwrap();
+    assert_eq!(x.dtype(), "int64");
+    let x = js_sys::BigInt64Array::from(x.to_typed_array()).to_vec();
+    assert!(x.iter().all(|&x| (low..low + 512).contains(&x)));
+    assert!(x.iter().any(|&x| x % 256 != 0));
+    assert!(generator
+        .integers(&shape(vec![1]), -1e19, Some(1e19), None)
+        .is_err());
+    assert!(generator
+        .integers(&shape(vec![1]), 0.0, Some(1e39), None)
+        .is_err());
+    assert!(generator
+        .integers(&shape(vec![1]), -1e300, Some(-1e299), None)
+        .is_err());
+    assert!(generator
+        .integers(
+            &shape(vec![1]),
+            0.0,
+            Some(2f64.powi(40)),
+            Some(String::from("int32"))
+        )
+        .is_err());
+    assert!(generator
+        .integers(
+            &shape(vec![1]),
+            -1.0,
+            Some(1.0),
+            Some(String::from("uint8"))
+        )
+        .is_err());
+    let x = generator
+        .integers(
+            &shape(vec![5]),
+            0.0,
+            Some(256.0),
+            Some(String::from("uint8")),
+        )
+        .unwrap();
+    assert_eq!(x.dtype(), "uint8");
+    let x = generator
+        .integers(&shape(vec![5]), 2f64.powi(63), Some(2f64.powi(64)), None)
+        .unwrap();
+    assert_eq!(x.dtype(), "uint64");
+    assert!(js_sys::BigUint64Array::from(x.to_typed_array())
+        .to_vec()
+        .iter()
+        .all(|&x| x >= 1 << 63));
+}
+
+#[wasm_bindgen_test]
+fn test_distributions() {
+    let mut generator = Generator::new(Some(7.0)).unwrap();
+    let size = shape(vec![20000]);
+    let x = values(&generator.normal(&size, Some(3.0), Some(2.0), None).unwrap());
+    assert!((mean(&x) - 3.0).abs() < 0.1);
+    let x = values(&generator.exponential(&size, Some(2.0), None).unwrap());
+    assert!(x.iter().all(|&x| x >= 0.0));
+    assert!((mean(&x) - 2.0).abs() < 0.1);
+    let x = values(&generator.gamma(&size, 0.5, Some(2.0), None).unwrap());
+    assert!((mean(&x) - 1.0).abs() < 0.05);
+    let x = values(&generator.beta(&size, 2.0, 6.0, None).unwrap());
+    assert!(x.iter().all(|&x| (0.0..=1.0).contains(&x)));
+    assert!((mean(&x) - 0.25).abs() < 0.01);
+
+    let x = generator.binomial(&size, 1000.0, 0.3, None).unwrap();
+    assert_eq!(x.dtype(), "int32");
+    assert!((mean(&values(&x)) - 300.0).abs() < 1.0);
+    let x = generator.poisson(&size, Some(100.0), None).unwrap();
+    assert_eq!(x.dtype(), "int32");
+    assert!((mean(&values(&x)) - 100.0).abs() < 0.5);
+
+    assert!(generator.normal(&size, None, Some(-1.0), None).is_err());
+    assert!(generator.gamma(&size, 0.0, None, None).is_err());
+    assert!(generator.beta(&size, 1.0, -1.0, None).is_err());
+    assert!(generator.binomial(&size, 10.0, 1.5, None).is_err());
+    assert!(generator.poisson(&size, Some(-1.0), None).is_err());
+    assert!(generator.random(&shape(vec![2, -3]), None).is_err());
+    let fractional = [JsValue::from(2.5)].iter().collect::<js_sys::Array>();
+    assert!(generator.normal(&fractional, None, None, None).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_permutation_shuffle() {
+    let mut generator = Generator::new(Some(3.0)).unwrap();
+    let x = generator.permutation(10.0, None).unwrap();
+    assert!(generator.permutation(-1.0, None).is_err());
+    assert!(generator.permutation(2.5, None).is_err());
+    assert_eq!(x.dtype(), "int32");
+    let mut x = values(&x);
+    x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(x, (0..10).map(|x| x as f64).collect::<Vec<f64>>());
+
+    let mut a = Ndarray::arange(12.0, None, None, None).unwrap();
+    a.reshape(&shape(vec![4, 3])).unwrap();
+    generator.shuffle(&mut a, None).unwrap();
+    assert!(generator.shuffle(&mut a, Some(2)).is_err());
+    // views share the data, which can't be shuffled then
+    let view = a.view();
+    assert!(generator.shuffle(&mut a, None).is_err());
+    drop(view);
+    let mut rows = values(&a)
+        .chunks(3)
+        .map(|row| {
+            // the elements of a row stay together
+            assert_eq!(row[1], row[0] + 1.0);
+            assert_eq!(row[2], row[0] + 2.0);
+            row[0]
+        })
+        .collect::<Vec<f64>>();
+    rows.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(rows, vec![0.0, 3.0, 6.0, 9.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_choice() {
+    let mut generator = Generator::new(Some(5.0)).unwrap();
+    let mut a = Ndarray::arange(10.0, Some(20.0), None, None).unwrap();
+    let x = generator
+        .choice(&a, None, None, JsValue::UNDEFINED)
+        .unwrap();
+    assert!((10.0..20.0).contains(&x.as_f64().unwrap()));
+
+    let x = to_ndarray(
+        generator
+            .choice(&a, Some(shape(vec![2, 5])), Some(false), JsValue::UNDEFINED)
+            .unwrap(),
+    );
+    assert_eq!(x.shape(), &vec![2, 5]);
+    let mut x = values(&x);
+    x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    x.dedup();
+    assert_eq!(x.len(), 10);
+    assert!(generator
+        .choice(&a, Some(shape(vec![11])), Some(false), JsValue::UNDEFINED)
+        .is_err());
+    assert!(generator
+        .choice(&a, Some(shape(vec![-1])), None, JsValue::UNDEFINED)
+        .is_err());
+
+    let mut p = Ndarray::zeros(&shape(vec![10]), None).unwrap();
+    p.reshape(&shape(vec![2, 5])).unwrap();
+    assert!(generator
+        .choice(&a, Some(shape(vec![3])), None, JsValue::from(p))
+        .is_err());
+    let p = Ndarray::new(JsValue::from(js_interop::test::vec_f64_into_float64array(
+        vec![0.0, 0.0, 0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.5],
+    )));
+    let x = to_ndarray(
+        generator
+            .choice(&a, Some(shape(vec![100])), None, JsValue::from(p.view()))
+            .unwrap(),
+    );
+    assert!(values(&x).iter().all(|&x| x == 12.0 || x == 19.0));
+    let x = to_ndarray(
+        generator
+            .choice(
+                &a,
+                Some(shape(vec![2])),
+                Some(false),
+                JsValue::from(p.view()),
+            )
+            .unwrap(),
+    );
+    let mut x = values(&x);
+    x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(x, vec![12.0, 19.0]);
+
+    // rows of two-dimensional Ndarrays are chosen as a whole
+    a.reshape(&shape(vec![5, 2])).unwrap();
+    let x = to_ndarray(
+        generator
+            .choice(&a, Some(shape(vec![3])), None, JsValue::UNDEFINED)
+            .unwrap(),
+    );
+    assert_eq!(x.shape(), &vec![3, 2]);
+    assert!(values(&x).chunks(2).all(|row| row[1] == row[0] + 1.0));
+}